@@ -0,0 +1,182 @@
+//! Binning of continuous columns, shared by mRMR and SURD
+//!
+//! Both analyses go through one [`Discretizer`] so a column binned for one is
+//! binned identically for the other and their importances stay comparable.
+
+use super::DiscretizationStrategy;
+use crate::error::{DeepCausalityError, Result};
+use deep_causality_tensor::CausalTensor;
+use polars::prelude::*;
+use serde::{Serialize, Deserialize};
+
+/// Lloyd iterations allowed before k-means binning settles for its current centroids
+const MAX_KMEANS_ITERATIONS: usize = 100;
+
+/// Bin count and strategy used to turn continuous columns into discrete states
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Discretizer {
+    pub n_bins: usize,
+    pub strategy: DiscretizationStrategy,
+}
+
+impl Discretizer {
+    pub fn new(n_bins: usize, strategy: DiscretizationStrategy) -> Self {
+        Self { n_bins, strategy }
+    }
+
+    /// Bin indices for one column, preserving nulls
+    pub fn bin(&self, values: &[Option<f64>]) -> Vec<Option<f64>> {
+        let n_bins = self.n_bins;
+        let mut present: Vec<f64> = values.iter().flatten().copied().filter(|v| v.is_finite()).collect();
+        if present.is_empty() || n_bins <= 1 {
+            return values.iter().map(|v| v.map(|_| 0.0)).collect();
+        }
+
+        // Interior cut points; a value falls into the bin counting the cuts at or below it
+        let cuts: Vec<f64> = match self.strategy {
+            DiscretizationStrategy::EqualWidth => {
+                let min = present.iter().copied().fold(f64::INFINITY, f64::min);
+                let max = present.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+                let width = (max - min) / n_bins as f64;
+                (1..n_bins).map(|k| min + width * k as f64).collect()
+            }
+            DiscretizationStrategy::EqualFrequency => {
+                present.sort_by(|a, b| a.total_cmp(b));
+                let n = present.len();
+                (1..n_bins).map(|k| present[(k * n / n_bins).min(n - 1)]).collect()
+            }
+            DiscretizationStrategy::KMeans => {
+                present.sort_by(|a, b| a.total_cmp(b));
+                kmeans_cuts(&present, n_bins)
+            }
+        };
+
+        values.iter()
+            .map(|v| v.map(|x| cuts.partition_point(|&c| c <= x).min(n_bins - 1) as f64))
+            .collect()
+    }
+
+    /// Replace every column except the target with its bin indices (column-major layout)
+    pub fn bin_tensor(&self, tensor: &CausalTensor<Option<f64>>, target_idx: usize) -> Result<CausalTensor<Option<f64>>> {
+        self.bin_tensor_keeping(tensor, &[target_idx])
+    }
+
+    /// Replace every column except those in `keep` (e.g. several targets) with its bin indices
+    pub fn bin_tensor_keeping(&self, tensor: &CausalTensor<Option<f64>>, keep: &[usize]) -> Result<CausalTensor<Option<f64>>> {
+        self.validate()?;
+        let height = tensor.shape()[0];
+        if height == 0 {
+            return Err(DeepCausalityError::TensorConversion("Cannot discretize an empty tensor".into()));
+        }
+
+        let mut data: Vec<Option<f64>> = Vec::with_capacity(tensor.as_slice().len());
+        for (col_idx, values) in tensor.as_slice().chunks(height).enumerate() {
+            if keep.contains(&col_idx) {
+                data.extend_from_slice(values);
+            } else {
+                data.extend(self.bin(values));
+            }
+        }
+
+        CausalTensor::new(data, tensor.shape().to_vec())
+            .map_err(|e| DeepCausalityError::TensorConversion(format!("Failed to rebuild discretized CausalTensor: {}", e)))
+    }
+
+    /// Replace every column except the target with its bin indices
+    pub fn bin_frame(&self, df: &DataFrame, target_col: &str) -> Result<DataFrame> {
+        self.validate()?;
+
+        let mut columns = Vec::with_capacity(df.width());
+        for series in df.get_columns() {
+            if series.name() == target_col {
+                columns.push(series.clone());
+                continue;
+            }
+            let values: Vec<Option<f64>> = series.cast(&DataType::Float64)?
+                .f64()?
+                .into_iter()
+                .collect();
+            columns.push(Series::new(series.name(), self.bin(&values)));
+        }
+
+        Ok(DataFrame::new(columns)?)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.n_bins == 0 {
+            return Err(DeepCausalityError::Config("n_bins must be at least 1".into()));
+        }
+        Ok(())
+    }
+}
+
+/// Cut points of a 1-D k-means clustering of sorted values. Centroids are seeded at
+/// evenly spaced quantiles, so the result is deterministic.
+fn kmeans_cuts(sorted: &[f64], k: usize) -> Vec<f64> {
+    let n = sorted.len();
+    let midpoints = |centroids: &[f64]| -> Vec<f64> {
+        centroids.windows(2).map(|w| (w[0] + w[1]) / 2.0).collect()
+    };
+
+    let mut centroids: Vec<f64> = (0..k).map(|j| sorted[((2 * j + 1) * n / (2 * k)).min(n - 1)]).collect();
+    for _ in 0..MAX_KMEANS_ITERATIONS {
+        // In one dimension the nearest centroid is decided by the midpoints between them
+        let cuts = midpoints(&centroids);
+        let mut sums = vec![0.0; k];
+        let mut counts = vec![0usize; k];
+        for &x in sorted {
+            let cluster = cuts.partition_point(|&c| c <= x);
+            sums[cluster] += x;
+            counts[cluster] += 1;
+        }
+
+        // An empty cluster keeps its centroid
+        let mut updated: Vec<f64> = (0..k)
+            .map(|j| if counts[j] > 0 { sums[j] / counts[j] as f64 } else { centroids[j] })
+            .collect();
+        updated.sort_by(|a, b| a.total_cmp(b));
+        if updated == centroids {
+            break;
+        }
+        centroids = updated;
+    }
+    midpoints(&centroids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::tensor_adapter::TensorAdapter;
+
+    #[test]
+    fn test_tensor_and_frame_binning_agree() -> Result<()> {
+        let df = df![
+            "Lactate" => [Some(0.8), Some(1.1), None, Some(2.4), Some(4.9), Some(9.7), Some(1.3), Some(3.3)],
+            "SepsisLabel" => [0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 0.0, 1.0]
+        ]?;
+        let discretizer = Discretizer::new(3, DiscretizationStrategy::EqualFrequency);
+
+        // mRMR bins the converted tensor, SURD bins the frame before converting
+        let (tensor, _) = TensorAdapter::df_to_tensor(&df)?;
+        let mrmr_view = discretizer.bin_tensor(&tensor, 1)?;
+        let (surd_view, _) = TensorAdapter::df_to_tensor(&discretizer.bin_frame(&df, "SepsisLabel")?)?;
+
+        assert_eq!(mrmr_view.as_slice(), surd_view.as_slice());
+        assert_eq!(&mrmr_view.as_slice()[..8], &[Some(0.0), Some(0.0), None, Some(1.0), Some(2.0), Some(2.0), Some(1.0), Some(2.0)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_kmeans_follows_clusters_despite_outlier() {
+        let values: Vec<Option<f64>> = [1.0, 1.2, 1.4, 5.0, 5.2, 5.4, 20.0].iter().map(|&v| Some(v)).collect();
+
+        // The outlier stretches equal-width bins until both clusters share one bin
+        let width = Discretizer::new(3, DiscretizationStrategy::EqualWidth).bin(&values);
+        assert_eq!(width[..6], [Some(0.0); 6]);
+
+        let kmeans = Discretizer::new(3, DiscretizationStrategy::KMeans).bin(&values);
+        let expected: Vec<Option<f64>> = [0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 2.0].iter().map(|&v| Some(v)).collect();
+        assert_eq!(kmeans, expected);
+        assert_eq!("kmeans".parse::<DiscretizationStrategy>(), Ok(DiscretizationStrategy::KMeans));
+    }
+}
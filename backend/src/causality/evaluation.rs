@@ -0,0 +1,162 @@
+//! Discrimination metrics for backtesting risk scores against ground truth
+
+use super::CausalDiscovery;
+use crate::error::{DeepCausalityError, Result};
+use serde::{Serialize, Deserialize};
+
+/// One operating point on a ROC curve
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct RocPoint {
+    pub threshold: f64,
+    pub false_positive_rate: f64,
+    pub true_positive_rate: f64,
+}
+
+/// Receiver operating characteristic curve with its area
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RocCurve {
+    pub points: Vec<RocPoint>,
+    pub auc: f64,
+}
+
+/// One operating point on a precision-recall curve
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct PrPoint {
+    pub threshold: f64,
+    pub recall: f64,
+    pub precision: f64,
+}
+
+/// Precision-recall curve with its average precision
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrCurve {
+    pub points: Vec<PrPoint>,
+    pub average_precision: f64,
+}
+
+impl CausalDiscovery {
+    /// ROC curve over every distinct score threshold (tied scores form a single step)
+    pub fn roc_curve(scores: &[f64], labels: &[bool]) -> Result<RocCurve> {
+        let steps = Self::threshold_steps(scores, labels)?;
+        let (positives, negatives) = Self::class_counts(labels);
+        if positives == 0 || negatives == 0 {
+            return Err(DeepCausalityError::Evaluation(format!(
+                "ROC curve is undefined: labels contain {} positives and {} negatives", positives, negatives
+            )));
+        }
+
+        let mut points = vec![RocPoint {
+            threshold: f64::INFINITY,
+            false_positive_rate: 0.0,
+            true_positive_rate: 0.0,
+        }];
+        points.extend(steps.iter().map(|&(threshold, tp, fp)| RocPoint {
+            threshold,
+            false_positive_rate: fp as f64 / negatives as f64,
+            true_positive_rate: tp as f64 / positives as f64,
+        }));
+
+        // Trapezoidal area; diagonal segments from ties count half
+        let auc = points.windows(2)
+            .map(|w| {
+                (w[1].false_positive_rate - w[0].false_positive_rate)
+                    * (w[1].true_positive_rate + w[0].true_positive_rate) / 2.0
+            })
+            .sum();
+
+        Ok(RocCurve { points, auc })
+    }
+
+    /// Precision-recall curve and average precision (step-wise, as in scikit-learn)
+    pub fn pr_curve(scores: &[f64], labels: &[bool]) -> Result<PrCurve> {
+        let steps = Self::threshold_steps(scores, labels)?;
+        let (positives, _) = Self::class_counts(labels);
+        if positives == 0 {
+            return Err(DeepCausalityError::Evaluation("PR curve is undefined: labels contain no positives".into()));
+        }
+
+        let mut points = vec![PrPoint {
+            threshold: f64::INFINITY,
+            recall: 0.0,
+            precision: 1.0,
+        }];
+        points.extend(steps.iter().map(|&(threshold, tp, fp)| PrPoint {
+            threshold,
+            recall: tp as f64 / positives as f64,
+            precision: tp as f64 / (tp + fp) as f64,
+        }));
+
+        let average_precision = points.windows(2)
+            .map(|w| (w[1].recall - w[0].recall) * w[1].precision)
+            .sum();
+
+        Ok(PrCurve { points, average_precision })
+    }
+
+    /// Cumulative (threshold, true positives, false positives) at each distinct score, highest first
+    fn threshold_steps(scores: &[f64], labels: &[bool]) -> Result<Vec<(f64, usize, usize)>> {
+        let invalid = |message: String| Err(DeepCausalityError::Evaluation(message));
+        if scores.len() != labels.len() {
+            return invalid(format!("Got {} scores but {} labels", scores.len(), labels.len()));
+        }
+        if scores.is_empty() {
+            return invalid("Cannot build a curve from no scores".into());
+        }
+        if scores.iter().any(|s| s.is_nan()) {
+            return invalid("Scores contain NaN".into());
+        }
+
+        let mut pairs: Vec<(f64, bool)> = scores.iter().copied().zip(labels.iter().copied()).collect();
+        pairs.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        let mut steps: Vec<(f64, usize, usize)> = Vec::new();
+        let (mut tp, mut fp) = (0, 0);
+        for (i, &(score, label)) in pairs.iter().enumerate() {
+            if label {
+                tp += 1;
+            } else {
+                fp += 1;
+            }
+            // Only emit a point once every sample sharing this score is counted
+            if pairs.get(i + 1).map_or(true, |next| next.0 != score) {
+                steps.push((score, tp, fp));
+            }
+        }
+
+        Ok(steps)
+    }
+
+    fn class_counts(labels: &[bool]) -> (usize, usize) {
+        let positives = labels.iter().filter(|&&l| l).count();
+        (positives, labels.len() - positives)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roc_and_pr_curves() -> Result<()> {
+        let scores = [0.9, 0.8, 0.7, 0.6];
+        let labels = [true, false, true, false];
+
+        let roc = CausalDiscovery::roc_curve(&scores, &labels)?;
+        assert_eq!(roc.points.len(), 5);
+        assert!((roc.auc - 0.75).abs() < 1e-12);
+
+        let pr = CausalDiscovery::pr_curve(&scores, &labels)?;
+        assert!((pr.average_precision - (0.5 + 0.5 * 2.0 / 3.0)).abs() < 1e-12);
+
+        // A single tied score is one step: the chance diagonal
+        let tied = CausalDiscovery::roc_curve(&[0.5, 0.5], &[true, false])?;
+        assert_eq!(tied.points.len(), 2);
+        assert!((tied.auc - 0.5).abs() < 1e-12);
+
+        // Degenerate label sets
+        assert!(CausalDiscovery::roc_curve(&scores, &[true; 4]).is_err());
+        assert!(CausalDiscovery::pr_curve(&scores, &[false; 4]).is_err());
+        assert!((CausalDiscovery::pr_curve(&scores, &[true; 4])?.average_precision - 1.0).abs() < 1e-12);
+        Ok(())
+    }
+}
@@ -1,13 +1,292 @@
+mod bootstrap;
+mod discretizer;
+mod evaluation;
+mod moments;
+mod mrmr;
+mod transfer_entropy;
+
+use crate::data::source::DataSource;
+use crate::error::{DeepCausalityError, Result};
 use crate::utils::tensor_adapter::TensorAdapter;
 use deep_causality_algorithms::mrmr::mrmr_features_selector;
 use deep_causality_algorithms::surd::{surd_states, SurdResult};
+use deep_causality_tensor::CausalTensor;
 use polars::prelude::*;
-use anyhow::{Result, Context};
-use tracing::info;
+use tracing::{info, warn};
 use serde::{Serialize, Deserialize};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+
+pub use bootstrap::BootstrapScore;
+pub use discretizer::Discretizer;
+pub use moments::CorrelationMoments;
+pub use mrmr::ProgressHook;
+pub use evaluation::{PrCurve, PrPoint, RocCurve, RocPoint};
+pub use transfer_entropy::{TransferEntropyOptions, TransferEntropyScore};
 
 pub struct CausalDiscovery;
 
+/// Strategy used to bin continuous columns into discrete states
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscretizationStrategy {
+    /// Bins of equal width between the column minimum and maximum
+    #[default]
+    EqualWidth,
+    /// Quantile bins holding roughly equal numbers of samples
+    EqualFrequency,
+    /// 1-D k-means clusters, cut midway between neighbouring centroids
+    #[serde(rename = "kmeans")]
+    KMeans,
+}
+
+impl std::str::FromStr for DiscretizationStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "equal_width" => Ok(Self::EqualWidth),
+            "equal_frequency" => Ok(Self::EqualFrequency),
+            "kmeans" => Ok(Self::KMeans),
+            other => Err(format!(
+                "unknown discretization '{}' (expected equal_width, equal_frequency or kmeans)", other
+            )),
+        }
+    }
+}
+
+/// The spelling `from_str` accepts, as used on the command line and in config files
+impl std::fmt::Display for DiscretizationStrategy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::EqualWidth => "equal_width",
+            Self::EqualFrequency => "equal_frequency",
+            Self::KMeans => "kmeans",
+        })
+    }
+}
+
+/// How mRMR measures a candidate's redundancy against the selected set
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RedundancyMode {
+    /// Mean absolute pairwise correlation with the selected features
+    #[default]
+    Pairwise,
+    /// Correlation with the selected set as a whole, via partial correlations.
+    /// Avoids double-counting mutually correlated features but costs a linear
+    /// solve per candidate per round.
+    PartialCorrelation,
+}
+
+/// How mRMR combines a candidate's relevance and redundancy into its score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MrmrScheme {
+    /// Relevance divided by redundancy (FCQ/MIQ), as in the upstream selector.
+    /// Scale-free, but a nearly uncorrelated candidate's score is dominated by
+    /// the redundancy floor.
+    #[default]
+    Quotient,
+    /// Relevance minus redundancy (FCD/MID). The F-statistic usually dwarfs a
+    /// correlation, so redundancy only breaks near-ties and selection stays close
+    /// to plain relevance ranking.
+    Difference,
+}
+
+/// Options controlling mRMR feature selection
+#[derive(Debug, Clone)]
+pub struct MrmrOptions {
+    /// Maximum number of features to select
+    pub max_features: usize,
+    /// Discretize feature columns before selection (None keeps raw values)
+    pub discretizer: Option<Discretizer>,
+    /// Redundancy measure; anything but `Pairwise` uses the native selector
+    pub redundancy: RedundancyMode,
+    /// Relevance/redundancy combination; anything but `Quotient` uses the native selector
+    pub scheme: MrmrScheme,
+    /// Clinically synonymous features; at most one member of each group is selected
+    pub feature_groups: Vec<Vec<String>>,
+    /// Stop selecting once the next feature scores below this cutoff
+    pub min_score: Option<f64>,
+    /// Restrict candidates to exactly these columns (the target is always kept)
+    pub include_columns: Option<Vec<String>>,
+    /// Never select these columns; cannot be combined with `include_columns`
+    pub exclude_columns: Vec<String>,
+    /// Rescale returned scores so the top feature is 1.0 (`min_score` still applies to raw scores)
+    pub normalize_scores: bool,
+    /// Score candidates on this many threads (0 = one per core); setting it uses the native selector
+    pub n_threads: Option<usize>,
+    /// Known covariates whose linear influence is removed from the target and every candidate
+    /// before ranking; they are never selected themselves
+    pub condition_on: Vec<String>,
+    /// One non-negative weight per row, applied to every correlation (e.g. to up-weight a
+    /// rare class); setting it uses the native selector
+    pub sample_weights: Option<Vec<f64>>,
+}
+
+impl MrmrOptions {
+    pub fn new(max_features: usize) -> Self {
+        Self {
+            max_features,
+            discretizer: None,
+            redundancy: RedundancyMode::Pairwise,
+            scheme: MrmrScheme::Quotient,
+            feature_groups: Vec::new(),
+            min_score: None,
+            include_columns: None,
+            exclude_columns: Vec::new(),
+            normalize_scores: false,
+            n_threads: None,
+            condition_on: Vec::new(),
+            sample_weights: None,
+        }
+    }
+
+    pub fn with_include_columns(mut self, columns: Vec<String>) -> Self {
+        self.include_columns = Some(columns);
+        self
+    }
+
+    pub fn with_exclude_columns(mut self, columns: Vec<String>) -> Self {
+        self.exclude_columns = columns;
+        self
+    }
+
+    pub fn with_feature_groups(mut self, groups: Vec<Vec<String>>) -> Self {
+        self.feature_groups = groups;
+        self
+    }
+
+    /// Whether these options need the native selector rather than the upstream one
+    pub fn needs_native_selector(&self) -> bool {
+        self.redundancy != RedundancyMode::Pairwise
+            || self.scheme != MrmrScheme::Quotient
+            || !self.feature_groups.is_empty()
+            || self.n_threads.is_some()
+            || self.sample_weights.is_some()
+    }
+
+    pub fn with_redundancy(mut self, redundancy: RedundancyMode) -> Self {
+        self.redundancy = redundancy;
+        self
+    }
+
+    pub fn with_scheme(mut self, scheme: MrmrScheme) -> Self {
+        self.scheme = scheme;
+        self
+    }
+
+    pub fn with_discretization(self, n_bins: usize, strategy: DiscretizationStrategy) -> Self {
+        self.with_discretizer(Discretizer::new(n_bins, strategy))
+    }
+
+    pub fn with_discretizer(mut self, discretizer: Discretizer) -> Self {
+        self.discretizer = Some(discretizer);
+        self
+    }
+
+    pub fn with_min_score(mut self, min_score: f64) -> Self {
+        self.min_score = Some(min_score);
+        self
+    }
+
+    pub fn with_normalize_scores(mut self, normalize: bool) -> Self {
+        self.normalize_scores = normalize;
+        self
+    }
+
+    pub fn with_threads(mut self, n_threads: usize) -> Self {
+        self.n_threads = Some(n_threads);
+        self
+    }
+
+    pub fn with_condition_on(mut self, columns: Vec<String>) -> Self {
+        self.condition_on = columns;
+        self
+    }
+
+    pub fn with_sample_weights(mut self, weights: Vec<f64>) -> Self {
+        self.sample_weights = Some(weights);
+        self
+    }
+
+    /// Whether a selection score clears the configured cutoff
+    pub(crate) fn accepts(&self, score: f64) -> bool {
+        self.min_score.map_or(true, |min| score >= min)
+    }
+}
+
+/// Options controlling SURD decomposition
+#[derive(Debug, Clone)]
+pub struct SurdOptions {
+    /// Discretize feature columns before decomposition (None keeps raw values)
+    pub discretizer: Option<Discretizer>,
+    /// Joint cells (every feature's bin plus the target state) with fewer samples count as
+    /// under-sampled
+    pub min_samples_per_bin: usize,
+    /// How the decomposition terms combine into the dual-analysis specificity score
+    pub score_weights: SurdScoreWeights,
+    /// Skip features the decomposition fails on instead of failing the whole analysis
+    pub skip_failures: bool,
+}
+
+impl SurdOptions {
+    pub fn new() -> Self {
+        Self {
+            discretizer: None,
+            min_samples_per_bin: 5,
+            score_weights: SurdScoreWeights::default(),
+            skip_failures: false,
+        }
+    }
+
+    pub fn with_skip_failures(mut self, skip_failures: bool) -> Self {
+        self.skip_failures = skip_failures;
+        self
+    }
+
+    pub fn with_score_weights(mut self, weights: SurdScoreWeights) -> Self {
+        self.score_weights = weights;
+        self
+    }
+
+    pub fn with_min_samples_per_bin(mut self, min_samples: usize) -> Self {
+        self.min_samples_per_bin = min_samples;
+        self
+    }
+
+    pub fn with_discretization(self, n_bins: usize, strategy: DiscretizationStrategy) -> Self {
+        self.with_discretizer(Discretizer::new(n_bins, strategy))
+    }
+
+    pub fn with_discretizer(mut self, discretizer: Discretizer) -> Self {
+        self.discretizer = Some(discretizer);
+        self
+    }
+}
+
+impl Default for SurdOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Relative weight of each SURD term in a combined driver score
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SurdScoreWeights {
+    pub redundant: f64,
+    pub unique: f64,
+    pub synergistic: f64,
+}
+
+impl Default for SurdScoreWeights {
+    /// Unique information only
+    fn default() -> Self {
+        Self { redundant: 0.0, unique: 1.0, synergistic: 0.0 }
+    }
+}
+
 /// Result from SURD analysis containing decomposed causal information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SurdAnalysisResult {
@@ -15,6 +294,55 @@ pub struct SurdAnalysisResult {
     pub unique_info: f64,
     pub synergistic_info: f64,
     pub total_info: f64,
+    /// Share of samples in adequately populated joint cells (only set when discretized)
+    #[serde(default)]
+    pub estimation_reliability: Option<f64>,
+    /// Feature combinations left out because the decomposition failed on them
+    #[serde(default)]
+    pub skipped: Vec<SkippedCombination>,
+    /// Each feature's share of the terms above
+    #[serde(default)]
+    pub per_feature: BTreeMap<String, FeatureSurdTerms>,
+}
+
+/// One feature's share of the SURD terms
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct FeatureSurdTerms {
+    pub redundant: f64,
+    pub unique: f64,
+    pub synergistic: f64,
+}
+
+/// A feature combination SURD skipped, and why
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkippedCombination {
+    pub features: Vec<String>,
+    pub reason: String,
+}
+
+impl SurdAnalysisResult {
+    /// Weighted share of the total information (0 when there is none)
+    pub fn combined_driver_score(&self, weights: &SurdScoreWeights) -> f64 {
+        if self.total_info <= 0.0 {
+            return 0.0;
+        }
+        (weights.redundant * self.redundant_info
+            + weights.unique * self.unique_info
+            + weights.synergistic * self.synergistic_info)
+            / self.total_info
+    }
+}
+
+/// Result from SURD analysis restricted to two features
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SurdPairResult {
+    pub feature_a: String,
+    pub feature_b: String,
+    pub redundant_info: f64,
+    pub unique_info_a: f64,
+    pub unique_info_b: f64,
+    pub synergistic_info: f64,
+    pub total_info: f64,
 }
 
 /// Result from dual SURD analysis comparing Sepsis vs Non-Sepsis
@@ -27,60 +355,532 @@ pub struct SurdDualResult {
     pub sepsis_specific_score: f64,           // Measure of how different sepsis drivers are
 }
 
+/// Rank movement of a single feature between two mRMR selections
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureRankChange {
+    pub name: String,
+    pub primary_rank: Option<usize>,
+    pub compare_rank: Option<usize>,
+    pub primary_score: Option<f64>,
+    pub compare_score: Option<f64>,
+}
+
+/// A candidate mRMR left out, and what kept it out
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RejectedFeature {
+    pub name: String,
+    /// F-statistic against the target
+    pub relevance: f64,
+    /// Highest absolute correlation with a feature selected by `round`
+    pub max_redundancy: f64,
+    /// The selected feature behind `max_redundancy`
+    pub blocked_by: Option<String>,
+    /// 1-based selection round: the one that selected a group member when `grouped_out`,
+    /// otherwise the last round the candidate was still in the pool (unselected candidates
+    /// stay in the pool until selection ends)
+    pub round: usize,
+    /// Dropped because a member of its feature group was selected
+    pub grouped_out: bool,
+}
+
+/// mRMR selection together with the reasons for every rejection
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MrmrExplanation {
+    pub selected: Vec<(String, f64)>,
+    /// Rejected candidates, most relevant first
+    pub rejected: Vec<RejectedFeature>,
+}
+
+/// How one candidate scored in a single mRMR selection round
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CandidateScore {
+    pub name: String,
+    /// F-statistic against the target
+    pub relevance: f64,
+    /// Redundancy with the features selected in earlier rounds (0 in the first round)
+    pub redundancy: f64,
+    pub score: f64,
+}
+
+/// One mRMR selection round: the feature it picked and every candidate's score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SelectionStep {
+    /// 1-based selection round
+    pub round: usize,
+    pub selected: String,
+    /// Candidates still in the pool this round, in column order
+    pub candidates: Vec<CandidateScore>,
+}
+
+/// Side-by-side comparison of two mRMR selections
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankingComparison {
+    pub primary_features: Vec<(String, f64)>,
+    pub compare_features: Vec<(String, f64)>,
+    pub only_primary: Vec<String>,
+    pub only_compare: Vec<String>,
+    pub changes: Vec<FeatureRankChange>,
+    pub feature_overlap: f64,
+}
+
 impl CausalDiscovery {
-    /// Run mRMR feature selection algorithm
-    pub fn run_mrmr(df: &DataFrame, target_col: &str, max_features: usize) -> Result<Vec<(String, f64)>> {
-        info!("Converting DataFrame to CausalTensor for mRMR...");
-        let (tensor, col_names) = TensorAdapter::df_to_tensor(df)?;
-        
-        // Find target column index
-        let target_idx = col_names.iter()
-            .position(|n| n == target_col)
-            .context(format!("Target column {} not found", target_col))?;
+    /// Run mRMR feature selection algorithm on any data source (a DataFrame, rows, a query...)
+    pub fn run_mrmr(source: &dyn DataSource, target_col: &str, options: &MrmrOptions) -> Result<Vec<(String, f64)>> {
+        info!("Converting data source to CausalTensor for mRMR...");
+        let (tensor, col_names) = source.into_tensor()?;
+        Self::run_mrmr_tensor(&tensor, &col_names, target_col, options)
+    }
+
+    /// Run mRMR on an already converted tensor (e.g. one loaded from a tensor cache)
+    pub fn run_mrmr_tensor(
+        tensor: &CausalTensor<Option<f64>>,
+        col_names: &[String],
+        target_col: &str,
+        options: &MrmrOptions,
+    ) -> Result<Vec<(String, f64)>> {
+        Self::with_mrmr_input(tensor, col_names, target_col, options, |tensor, col_names, target_idx| {
+            info!("Running mRMR feature selection...");
+            let result: Vec<(String, f64)> = Self::select_features(tensor, col_names, target_col, target_idx, options)?
+                .into_iter()
+                .map(|(idx, score)| (col_names[idx].clone(), score))
+                .collect();
+
+            if options.normalize_scores {
+                return Ok(Self::normalize_scores(&result));
+            }
+            Ok(result)
+        })
+    }
+
+    /// Like [`Self::run_mrmr_tensor`] (same selector, raw scores), also reporting every
+    /// selected feature's relevance and its redundancy with the features selected before it
+    pub fn run_mrmr_tensor_described(
+        tensor: &CausalTensor<Option<f64>>,
+        col_names: &[String],
+        target_col: &str,
+        options: &MrmrOptions,
+    ) -> Result<Vec<CandidateScore>> {
+        Self::with_mrmr_input(tensor, col_names, target_col, options, |tensor, col_names, target_idx| {
+            let selected = Self::select_features(tensor, col_names, target_col, target_idx, options)?;
+            Ok(mrmr::describe_selection(tensor, target_idx, &selected, options)?
+                .into_iter()
+                .map(|c| CandidateScore {
+                    name: col_names[c.index].clone(),
+                    relevance: c.relevance,
+                    redundancy: c.redundancy,
+                    score: c.score,
+                })
+                .collect())
+        })
+    }
 
-        info!("Running mRMR feature selection...");
-        let selected_features = mrmr_features_selector(&tensor, max_features, target_idx)
-            .map_err(|e| anyhow::anyhow!("mRMR execution failed: {:?}", e))?;
+    /// Upstream selector unless the options need the native one, cut at `min_score`
+    fn select_features(
+        tensor: &CausalTensor<Option<f64>>,
+        col_names: &[String],
+        target_col: &str,
+        target_idx: usize,
+        options: &MrmrOptions,
+    ) -> Result<Vec<(usize, f64)>> {
+        let selected_features = if options.needs_native_selector() {
+            let groups = Self::resolve_feature_groups(&options.feature_groups, col_names, target_col)?;
+            mrmr::select_features(tensor, target_idx, options, &groups)?
+        } else {
+            mrmr_features_selector(tensor, options.max_features, target_idx)
+                .map_err(|e| DeepCausalityError::Mrmr(format!("{:?}", e)))?
+        };
+
+        // Scores are produced greedily, so everything after the first miss is cut too
+        Ok(selected_features.into_iter()
+            .take_while(|(_, score)| options.accepts(*score))
+            .collect())
+    }
 
-        // Map indices back to names
-        let result: Vec<(String, f64)> = selected_features.into_iter()
-            .map(|(idx, score)| (col_names[idx].clone(), score))
+    /// Run mRMR on a converted tensor, recording every candidate's relevance, redundancy
+    /// and score in every round (raw scores; `normalize_scores` does not apply).
+    pub fn run_mrmr_tensor_traced(
+        tensor: &CausalTensor<Option<f64>>,
+        col_names: &[String],
+        target_col: &str,
+        options: &MrmrOptions,
+        on_progress: ProgressHook,
+    ) -> Result<Vec<SelectionStep>> {
+        Self::with_mrmr_input(tensor, col_names, target_col, options, |tensor, col_names, target_idx| {
+            let groups = Self::resolve_feature_groups(&options.feature_groups, col_names, target_col)?;
+            let steps = mrmr::select_with_trace(tensor, target_idx, options, &groups, on_progress)?;
+            Ok(Self::named_steps(steps, col_names))
+        })
+    }
+
+    /// Attach column names to the selector's index-based trace
+    fn named_steps(steps: Vec<mrmr::Step>, col_names: &[String]) -> Vec<SelectionStep> {
+        steps.into_iter()
+            .enumerate()
+            .map(|(i, step)| SelectionStep {
+                round: i + 1,
+                selected: col_names[step.chosen].clone(),
+                candidates: step.candidates.into_iter()
+                    .map(|c| CandidateScore {
+                        name: col_names[c.index].clone(),
+                        relevance: c.relevance,
+                        redundancy: c.redundancy,
+                        score: c.score,
+                    })
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Run traced mRMR for several targets in one pass over the tensor, returning
+    /// (target, steps) in the given order.
+    ///
+    /// Column statistics and correlations are computed once for all targets, and no
+    /// target is offered as a candidate for another. Progress covers the whole run.
+    pub fn run_mrmr_tensor_multi_target(
+        tensor: &CausalTensor<Option<f64>>,
+        col_names: &[String],
+        targets: &[String],
+        options: &MrmrOptions,
+        on_progress: ProgressHook,
+    ) -> Result<Vec<(String, Vec<SelectionStep>)>> {
+        if targets.is_empty() {
+            return Err(DeepCausalityError::Mrmr("No target columns given".into()));
+        }
+        Self::with_multi_target_input(tensor, col_names, targets, options, |tensor, col_names, target_indices| {
+            // Groups resolve to the same indices for every target, but none may contain one
+            for target in &targets[1..] {
+                Self::resolve_feature_groups(&options.feature_groups, col_names, target)?;
+            }
+            let groups = Self::resolve_feature_groups(&options.feature_groups, col_names, &targets[0])?;
+            let runs = mrmr::select_multi_target_with_trace(tensor, target_indices, options, &groups, on_progress)?;
+            Ok(targets.iter()
+                .cloned()
+                .zip(runs.into_iter().map(|steps| Self::named_steps(steps, col_names)))
+                .collect())
+        })
+    }
+
+    /// Pairwise mutual information (bits) between every column, from the same Pearson
+    /// correlations mRMR scores with, as an N x N matrix with a NaN diagonal. Pairs are
+    /// computed on `n_threads` threads when set (0 = one per core).
+    pub fn information_matrix(tensor: &CausalTensor<Option<f64>>, n_threads: Option<usize>) -> Result<Vec<Vec<f64>>> {
+        mrmr::information_matrix(tensor, n_threads)
+    }
+
+    /// Run traced mRMR for several targets from correlation moments accumulated over row
+    /// chunks, returning (target, steps) in the given order.
+    ///
+    /// Scores match a run on the full tensor. Column filters and feature groups apply;
+    /// discretization and conditioning need all rows at once and are rejected.
+    pub fn run_mrmr_moments(
+        moments: &CorrelationMoments,
+        col_names: &[String],
+        targets: &[String],
+        options: &MrmrOptions,
+        on_progress: ProgressHook,
+    ) -> Result<Vec<(String, Vec<SelectionStep>)>> {
+        if col_names.len() != moments.n_cols() {
+            return Err(DeepCausalityError::Mrmr(format!(
+                "Got {} column names for moments over {} columns", col_names.len(), moments.n_cols()
+            )));
+        }
+        if targets.is_empty() {
+            return Err(DeepCausalityError::Mrmr("No target columns given".into()));
+        }
+        if options.discretizer.is_some() || !options.condition_on.is_empty() || options.sample_weights.is_some() {
+            return Err(DeepCausalityError::Mrmr(
+                "Discretization, conditioning and sample weights are not supported on chunked data".into()
+            ));
+        }
+
+        let target_indices = targets.iter()
+            .map(|target| col_names.iter()
+                .position(|n| n == target)
+                .ok_or_else(|| DeepCausalityError::TargetNotFound(target.clone())))
+            .collect::<Result<Vec<usize>>>()?;
+        // Filtered-out columns stay in the moments and are just never offered
+        let excluded: Vec<usize> = match Self::candidate_columns(col_names, targets, options)? {
+            Some(keep) => (0..col_names.len()).filter(|i| !keep.contains(i)).collect(),
+            None => Vec::new(),
+        };
+        for target in &targets[1..] {
+            Self::resolve_feature_groups(&options.feature_groups, col_names, target)?;
+        }
+        let groups = Self::resolve_feature_groups(&options.feature_groups, col_names, &targets[0])?;
+
+        let runs = mrmr::select_multi_target_from_moments(
+            moments, &target_indices, &excluded, options, &groups, on_progress,
+        )?;
+        Ok(targets.iter()
+            .cloned()
+            .zip(runs.into_iter().map(|steps| Self::named_steps(steps, col_names)))
+            .collect())
+    }
+
+    /// Run mRMR and explain why every other candidate was left out.
+    pub fn run_mrmr_with_rejections(
+        source: &dyn DataSource,
+        target_col: &str,
+        options: &MrmrOptions,
+    ) -> Result<MrmrExplanation> {
+        let (tensor, col_names) = source.into_tensor()?;
+        Self::with_mrmr_input(&tensor, &col_names, target_col, options, |tensor, col_names, target_idx| {
+            let groups = Self::resolve_feature_groups(&options.feature_groups, col_names, target_col)?;
+            let (selected, rejections) = mrmr::select_with_rejections(tensor, target_idx, options, &groups)?;
+
+            let mut rejected: Vec<RejectedFeature> = rejections.into_iter()
+                .map(|r| RejectedFeature {
+                    name: col_names[r.index].clone(),
+                    relevance: r.relevance,
+                    max_redundancy: r.max_redundancy,
+                    blocked_by: r.blocked_by.map(|i| col_names[i].clone()),
+                    round: r.round,
+                    grouped_out: r.grouped_out,
+                })
+                .collect();
+            rejected.sort_by(|a, b| b.relevance.total_cmp(&a.relevance));
+
+            Ok(MrmrExplanation {
+                selected: selected.into_iter().map(|(idx, score)| (col_names[idx].clone(), score)).collect(),
+                rejected,
+            })
+        })
+    }
+
+    /// Validate names and target, apply column filters and discretization, then hand the
+    /// prepared tensor, its column names and the target index to `run`
+    fn with_mrmr_input<T>(
+        tensor: &CausalTensor<Option<f64>>,
+        col_names: &[String],
+        target_col: &str,
+        options: &MrmrOptions,
+        run: impl FnOnce(&CausalTensor<Option<f64>>, &[String], usize) -> Result<T>,
+    ) -> Result<T> {
+        Self::with_multi_target_input(tensor, col_names, &[target_col.to_string()], options, |tensor, col_names, targets| {
+            run(tensor, col_names, targets[0])
+        })
+    }
+
+    /// [`Self::with_mrmr_input`] for several targets; every target stays in the prepared
+    /// tensor undiscretized and `run` gets their indices in the given order. With
+    /// `condition_on`, `run` sees residuals and the covariates are no longer in the tensor.
+    fn with_multi_target_input<T>(
+        tensor: &CausalTensor<Option<f64>>,
+        col_names: &[String],
+        targets: &[String],
+        options: &MrmrOptions,
+        run: impl FnOnce(&CausalTensor<Option<f64>>, &[String], &[usize]) -> Result<T>,
+    ) -> Result<T> {
+        // A names/columns mismatch would silently attach scores to the wrong features
+        let n_cols = tensor.shape().get(1).copied().unwrap_or(0);
+        if col_names.len() != n_cols {
+            return Err(DeepCausalityError::Mrmr(format!(
+                "Got {} column names for a tensor with {} columns", col_names.len(), n_cols
+            )));
+        }
+
+        if let Some(missing) = targets.iter().find(|t| !col_names.contains(*t)) {
+            return Err(DeepCausalityError::TargetNotFound(missing.clone()));
+        }
+        if let Some(missing) = options.condition_on.iter().find(|c| !col_names.contains(*c)) {
+            return Err(DeepCausalityError::Mrmr(format!("Conditioning column {} not found", missing)));
+        }
+        if let Some(target) = options.condition_on.iter().find(|c| targets.contains(*c)) {
+            return Err(DeepCausalityError::Mrmr(format!("Cannot condition {} on itself", target)));
+        }
+        // A repeated covariate would otherwise surface as a collinearity error
+        if let Some(repeated) = options.condition_on.iter().enumerate()
+            .find_map(|(i, c)| options.condition_on[..i].contains(c).then_some(c))
+        {
+            return Err(DeepCausalityError::Mrmr(format!("Conditioning column {} is listed more than once", repeated)));
+        }
+
+        // Covariates survive the column filter; they are dropped once conditioned on
+        let kept: Vec<String> = targets.iter().chain(&options.condition_on).cloned().collect();
+        let restricted;
+        let restricted_names: Vec<String>;
+        let (tensor, col_names) = match Self::candidate_columns(col_names, &kept, options)? {
+            Some(keep) => {
+                info!("Restricting mRMR to {} candidate columns", keep.len() - targets.len());
+                restricted = Self::select_tensor_columns(tensor, &keep)?;
+                restricted_names = keep.iter().map(|&i| col_names[i].clone()).collect();
+                (&restricted, restricted_names.as_slice())
+            }
+            None => (tensor, col_names),
+        };
+
+        // Find target column indices
+        let target_indices = targets.iter()
+            .map(|target| col_names.iter()
+                .position(|n| n == target)
+                .ok_or_else(|| DeepCausalityError::TargetNotFound(target.clone())))
+            .collect::<Result<Vec<usize>>>()?;
+
+        let kept_indices: Vec<usize> = kept.iter()
+            .filter_map(|name| col_names.iter().position(|n| n == name))
             .collect();
+        let binned;
+        let tensor = match options.discretizer {
+            Some(discretizer) => {
+                info!("Discretizing features into {} bins ({:?})...", discretizer.n_bins, discretizer.strategy);
+                binned = discretizer.bin_tensor_keeping(tensor, &kept_indices)?;
+                &binned
+            }
+            None => tensor,
+        };
 
-        Ok(result)
+        if options.condition_on.is_empty() {
+            return run(tensor, col_names, &target_indices);
+        }
+
+        info!("Conditioning on {} covariates...", options.condition_on.len());
+        let covariates = &kept_indices[targets.len()..];
+        let residuals = mrmr::residualize(tensor, covariates, col_names)?;
+        let keep: Vec<usize> = (0..col_names.len()).filter(|i| !covariates.contains(i)).collect();
+        let conditioned = Self::select_tensor_columns(&residuals, &keep)?;
+        let conditioned_names: Vec<String> = keep.iter().map(|&i| col_names[i].clone()).collect();
+        // Targets shift left by the number of covariates dropped before them
+        let target_indices: Vec<usize> = target_indices.iter()
+            .map(|&t| t - covariates.iter().filter(|&&c| c < t).count())
+            .collect();
+        run(&conditioned, &conditioned_names, &target_indices)
     }
 
     /// Run SURD (Synergistic Unique Redundant Degree) analysis
     /// Returns decomposed information: Redundant, Unique, Synergistic
-    pub fn run_surd(df: &DataFrame, target_col: &str) -> Result<SurdAnalysisResult> {
+    pub fn run_surd(df: &DataFrame, target_col: &str, options: &SurdOptions) -> Result<SurdAnalysisResult> {
+        let df = Self::prepare_frame(df, target_col, options.discretizer)?;
+
         info!("Converting DataFrame to CausalTensor for SURD...");
-        let (tensor, col_names) = TensorAdapter::df_to_tensor(df)?;
+        let (tensor, col_names) = TensorAdapter::df_to_tensor(&df)?;
 
         // Find target column index
         let target_idx = col_names.iter()
             .position(|n| n == target_col)
-            .context(format!("Target column {} not found", target_col))?;
+            .ok_or_else(|| DeepCausalityError::TargetNotFound(target_col.to_string()))?;
 
         // Get feature indices (all columns except target)
-        let agent_indices: Vec<usize> = (0..col_names.len())
+        let mut agent_indices: Vec<usize> = (0..col_names.len())
             .filter(|&i| i != target_idx)
             .collect();
 
+        let mut skipped: Vec<SkippedCombination> = Vec::new();
+        let mut skip = |i: usize, reason: String| {
+            warn!("Skipping {} in SURD: {}", col_names[i], reason);
+            skipped.push(SkippedCombination { features: vec![col_names[i].clone()], reason });
+        };
+
+        if options.skip_failures {
+            let height = tensor.shape()[0].max(1);
+            let columns: Vec<&[Option<f64>]> = tensor.as_slice().chunks(height).collect();
+            agent_indices.retain(|&i| match Self::column_problem(columns[i]) {
+                Some(reason) => {
+                    skip(i, reason.to_string());
+                    false
+                }
+                None => true,
+            });
+        }
+
         info!("Running SURD causal discovery with {} features...", agent_indices.len());
         
         // Call SURD algorithm
-        let surd_result = surd_states(&tensor, target_idx, &agent_indices)
-            .map_err(|e| anyhow::anyhow!("SURD execution failed: {:?}", e))?;
+        let surd_result = match surd_states(&tensor, target_idx, &agent_indices) {
+            Ok(result) => result,
+            Err(e) if options.skip_failures => {
+                // Isolate the features the decomposition fails on, then retry without them
+                warn!("SURD failed on the full feature set ({:?}); isolating failing features", e);
+                agent_indices.retain(|&i| match surd_states(&tensor, target_idx, &[i]) {
+                    Ok(_) => true,
+                    Err(e) => {
+                        skip(i, format!("{:?}", e));
+                        false
+                    }
+                });
+                if agent_indices.is_empty() {
+                    return Err(DeepCausalityError::Surd("decomposition failed for every feature".into()));
+                }
+                surd_states(&tensor, target_idx, &agent_indices)
+                    .map_err(|e| DeepCausalityError::Surd(format!("{:?}", e)))?
+            }
+            Err(e) => return Err(DeepCausalityError::Surd(format!("{:?}", e))),
+        };
 
         // Aggregate SURD results
         let (redundant, unique, synergistic) = Self::aggregate_surd_result(&surd_result);
         let total = redundant + unique + synergistic;
 
+        let estimation_reliability = match options.discretizer {
+            Some(_) => {
+                let reliability = Self::estimation_reliability(
+                    &tensor, target_idx, &agent_indices, options.min_samples_per_bin,
+                );
+                if reliability < 1.0 {
+                    warn!(
+                        "{:.1}% of samples fall in joint cells with fewer than {} samples; SURD terms may be unreliable",
+                        (1.0 - reliability) * 100.0,
+                        options.min_samples_per_bin
+                    );
+                }
+                Some(reliability)
+            }
+            None => None,
+        };
+
         Ok(SurdAnalysisResult {
             redundant_info: redundant,
             unique_info: unique,
             synergistic_info: synergistic,
             total_info: total,
+            estimation_reliability,
+            skipped,
+            per_feature: Self::surd_by_feature(&surd_result, &col_names, &agent_indices),
+        })
+    }
+
+    /// Run SURD on exactly two features to isolate their joint (synergistic) information
+    pub fn run_surd_pair(
+        df: &DataFrame,
+        target_col: &str,
+        feature_a: &str,
+        feature_b: &str,
+        options: &SurdOptions,
+    ) -> Result<SurdPairResult> {
+        for feature in [feature_a, feature_b] {
+            if feature == target_col {
+                return Err(DeepCausalityError::Surd(format!("Feature {} is the target column", feature)));
+            }
+            if df.column(feature).is_err() {
+                return Err(DeepCausalityError::Surd(format!("Feature column {} not found", feature)));
+            }
+        }
+        if feature_a == feature_b {
+            return Err(DeepCausalityError::Surd(format!(
+                "SURD pair needs two distinct features, got {} twice", feature_a
+            )));
+        }
+
+        let pair_df = df.select([feature_a, feature_b, target_col])
+            .map_err(|_| DeepCausalityError::TargetNotFound(target_col.to_string()))?;
+        let pair_df = Self::prepare_frame(&pair_df, target_col, options.discretizer)?;
+        let (tensor, _) = TensorAdapter::df_to_tensor(&pair_df)?;
+
+        info!("Running pairwise SURD on {} and {}...", feature_a, feature_b);
+        let surd_result = surd_states(&tensor, 2, &[0, 1])
+            .map_err(|e| DeepCausalityError::Surd(format!("{:?}", e)))?;
+
+        let (redundant, _, synergistic) = Self::aggregate_surd_result(&surd_result);
+        let unique_a = surd_result.mutual_info().get([0usize].as_slice()).copied().unwrap_or(0.0);
+        let unique_b = surd_result.mutual_info().get([1usize].as_slice()).copied().unwrap_or(0.0);
+
+        Ok(SurdPairResult {
+            feature_a: feature_a.to_string(),
+            feature_b: feature_b.to_string(),
+            redundant_info: redundant,
+            unique_info_a: unique_a,
+            unique_info_b: unique_b,
+            synergistic_info: synergistic,
+            total_info: redundant + unique_a + unique_b + synergistic,
         })
     }
 
@@ -88,21 +888,22 @@ impl CausalDiscovery {
     pub fn run_surd_dual(
         sepsis_df: &DataFrame, 
         non_sepsis_df: &DataFrame, 
-        target_col: &str
+        target_col: &str,
+        options: &SurdOptions,
     ) -> Result<SurdDualResult> {
         info!("=== SURD Dual Analysis: Sepsis vs Non-Sepsis ===");
         
         // Analyze Sepsis subset
         info!("Analyzing Sepsis subset ({} rows)...", sepsis_df.height());
-        let sepsis_result = Self::run_surd(sepsis_df, target_col)?;
+        let sepsis_result = Self::run_surd(sepsis_df, target_col, options)?;
         
         // Analyze Non-Sepsis subset  
         info!("Analyzing Non-Sepsis subset ({} rows)...", non_sepsis_df.height());
-        let non_sepsis_result = Self::run_surd(non_sepsis_df, target_col)?;
+        let non_sepsis_result = Self::run_surd(non_sepsis_df, target_col, options)?;
 
         // Run mRMR on both to identify feature rankings
-        let sepsis_features = Self::run_mrmr(sepsis_df, target_col, 15)?;
-        let non_sepsis_features = Self::run_mrmr(non_sepsis_df, target_col, 15)?;
+        let sepsis_features = Self::run_mrmr(sepsis_df, target_col, &MrmrOptions::new(15))?;
+        let non_sepsis_features = Self::run_mrmr(non_sepsis_df, target_col, &MrmrOptions::new(15))?;
 
         // Find disjoint (sepsis-only) and shared drivers
         let sepsis_names: std::collections::HashSet<_> = sepsis_features.iter()
@@ -119,14 +920,9 @@ impl CausalDiscovery {
             .cloned()
             .collect();
 
-        // Calculate sepsis specificity score (unique/total ratio difference)
-        let sepsis_unique_ratio = if sepsis_result.total_info > 0.0 {
-            sepsis_result.unique_info / sepsis_result.total_info
-        } else { 0.0 };
-        let non_sepsis_unique_ratio = if non_sepsis_result.total_info > 0.0 {
-            non_sepsis_result.unique_info / non_sepsis_result.total_info
-        } else { 0.0 };
-        let sepsis_specific_score = (sepsis_unique_ratio - non_sepsis_unique_ratio).abs();
+        // Sepsis specificity: difference in weighted share of information (unique/total by default)
+        let sepsis_specific_score = (sepsis_result.combined_driver_score(&options.score_weights)
+            - non_sepsis_result.combined_driver_score(&options.score_weights)).abs();
 
         Ok(SurdDualResult {
             sepsis_result,
@@ -137,8 +933,233 @@ impl CausalDiscovery {
         })
     }
 
+    /// Map feature group names to column indices, rejecting unknown, target, or shared members
+    fn resolve_feature_groups(groups: &[Vec<String>], col_names: &[String], target_col: &str) -> Result<Vec<Vec<usize>>> {
+        let mut seen = std::collections::HashSet::new();
+        groups.iter()
+            .map(|group| {
+                group.iter()
+                    .map(|name| {
+                        if name == target_col {
+                            return Err(DeepCausalityError::Mrmr(format!("Feature group member {} is the target column", name)));
+                        }
+                        if !seen.insert(name.as_str()) {
+                            return Err(DeepCausalityError::Mrmr(format!("Feature {} appears in more than one group", name)));
+                        }
+                        col_names.iter()
+                            .position(|n| n == name)
+                            .ok_or_else(|| DeepCausalityError::Mrmr(format!("Feature group member {} not found", name)))
+                    })
+                    .collect::<Result<Vec<usize>>>()
+            })
+            .collect()
+    }
+
+    /// Column indices (target included) left after applying include/exclude filters,
+    /// or `None` when no filter is set
+    fn candidate_columns(col_names: &[String], targets: &[String], options: &MrmrOptions) -> Result<Option<Vec<usize>>> {
+        // `include` is true when `listed` is an allow-list
+        let (listed, include) = match (&options.include_columns, options.exclude_columns.is_empty()) {
+            (None, true) => return Ok(None),
+            (Some(include), true) => (include, true),
+            (None, false) => (&options.exclude_columns, false),
+            (Some(_), false) => {
+                return Err(DeepCausalityError::Mrmr("include_columns and exclude_columns cannot both be set".into()));
+            }
+        };
+
+        if let Some(missing) = listed.iter().find(|name| !col_names.contains(*name)) {
+            return Err(DeepCausalityError::Mrmr(format!("Filtered column {} not found", missing)));
+        }
+
+        let keep = (0..col_names.len())
+            .filter(|&i| targets.contains(&col_names[i]) || listed.contains(&col_names[i]) == include)
+            .collect();
+        Ok(Some(keep))
+    }
+
+    /// Copy the given columns (column-major) into a new tensor
+    fn select_tensor_columns(tensor: &CausalTensor<Option<f64>>, keep: &[usize]) -> Result<CausalTensor<Option<f64>>> {
+        let height = tensor.shape()[0];
+        let data: Vec<Option<f64>> = keep.iter()
+            .flat_map(|&i| tensor.as_slice()[i * height..(i + 1) * height].iter().copied())
+            .collect();
+        CausalTensor::new(data, vec![height, keep.len()])
+            .map_err(|e| DeepCausalityError::TensorConversion(format!("Failed to build filtered CausalTensor: {}", e)))
+    }
+
+    /// Turn an empty selection into a `NoFeaturesSelected` error explaining the likely causes
+    pub fn ensure_selected(features: &[(String, f64)], target_col: &str) -> Result<()> {
+        if features.is_empty() {
+            return Err(DeepCausalityError::NoFeaturesSelected(target_col.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Divide every score by the highest one so rankings from different runs share a 0..1 scale.
+    /// Scores are returned unchanged when the highest is not positive.
+    pub fn normalize_scores(features: &[(String, f64)]) -> Vec<(String, f64)> {
+        let top = features.iter().map(|(_, score)| *score).fold(f64::NEG_INFINITY, f64::max);
+        if !(top > 0.0 && top.is_finite()) {
+            return features.to_vec();
+        }
+        features.iter().map(|(name, score)| (name.clone(), score / top)).collect()
+    }
+
+    /// Jaccard overlap of two selected feature sets (1.0 = identical, 0.0 = disjoint)
+    pub fn feature_overlap(a: &[(String, f64)], b: &[(String, f64)]) -> f64 {
+        let a_names: std::collections::HashSet<_> = a.iter().map(|(n, _)| n.as_str()).collect();
+        let b_names: std::collections::HashSet<_> = b.iter().map(|(n, _)| n.as_str()).collect();
+
+        let union = a_names.union(&b_names).count();
+        if union == 0 {
+            return 1.0;
+        }
+        a_names.intersection(&b_names).count() as f64 / union as f64
+    }
+
+    /// Compare two mRMR selections feature by feature (ranks are 1-based)
+    pub fn compare_rankings(primary: &[(String, f64)], compare: &[(String, f64)]) -> RankingComparison {
+        let lookup = |features: &[(String, f64)], name: &str| {
+            features.iter()
+                .position(|(n, _)| n == name)
+                .map(|i| (i + 1, features[i].1))
+        };
+
+        // Primary order first, then features that only the comparison selected
+        let mut names: Vec<&str> = primary.iter().map(|(n, _)| n.as_str()).collect();
+        for (name, _) in compare {
+            if !names.contains(&name.as_str()) {
+                names.push(name);
+            }
+        }
+
+        let changes: Vec<FeatureRankChange> = names.iter()
+            .map(|&name| {
+                let p = lookup(primary, name);
+                let c = lookup(compare, name);
+                FeatureRankChange {
+                    name: name.to_string(),
+                    primary_rank: p.map(|(r, _)| r),
+                    compare_rank: c.map(|(r, _)| r),
+                    primary_score: p.map(|(_, s)| s),
+                    compare_score: c.map(|(_, s)| s),
+                }
+            })
+            .collect();
+
+        RankingComparison {
+            primary_features: primary.to_vec(),
+            compare_features: compare.to_vec(),
+            only_primary: changes.iter()
+                .filter(|c| c.compare_rank.is_none())
+                .map(|c| c.name.clone())
+                .collect(),
+            only_compare: changes.iter()
+                .filter(|c| c.primary_rank.is_none())
+                .map(|c| c.name.clone())
+                .collect(),
+            feature_overlap: Self::feature_overlap(primary, compare),
+            changes,
+        }
+    }
+
+    /// Discretize a column into bin indices, preserving nulls
+    pub fn discretize(values: &[Option<f64>], n_bins: usize, strategy: DiscretizationStrategy) -> Vec<Option<f64>> {
+        Discretizer::new(n_bins, strategy).bin(values)
+    }
+
+    /// Apply the requested discretization, borrowing the frame untouched when there is none
+    fn prepare_frame<'a>(
+        df: &'a DataFrame,
+        target_col: &str,
+        discretizer: Option<Discretizer>,
+    ) -> Result<Cow<'a, DataFrame>> {
+        match discretizer {
+            Some(discretizer) => {
+                info!("Discretizing features into {} bins ({:?})...", discretizer.n_bins, discretizer.strategy);
+                Ok(Cow::Owned(discretizer.bin_frame(df, target_col)?))
+            }
+            None => Ok(Cow::Borrowed(df)),
+        }
+    }
+
+    /// Fraction of complete rows whose joint cell (the bins of every feature in
+    /// `agent_indices` plus the target state, as SURD's histogram sees them) holds at
+    /// least `min_samples` samples
+    fn estimation_reliability(
+        tensor: &CausalTensor<Option<f64>>,
+        target_idx: usize,
+        agent_indices: &[usize],
+        min_samples: usize,
+    ) -> f64 {
+        let height = tensor.shape()[0].max(1);
+        let columns: Vec<&[Option<f64>]> = tensor.as_slice().chunks(height).collect();
+
+        // One cell per combination of every feature's bin and the target state
+        let mut cells: HashMap<Vec<u64>, usize> = HashMap::new();
+        for row in 0..tensor.shape()[0] {
+            let key: Option<Vec<u64>> = agent_indices.iter()
+                .chain(std::iter::once(&target_idx))
+                .map(|&col| columns[col][row].map(f64::to_bits))
+                .collect();
+            if let Some(key) = key {
+                *cells.entry(key).or_insert(0) += 1;
+            }
+        }
+
+        let observed: usize = cells.values().sum();
+        if observed == 0 {
+            return 0.0;
+        }
+        let well_sampled: usize = cells.values().filter(|&&c| c >= min_samples).sum();
+        well_sampled as f64 / observed as f64
+    }
+
+    /// Why a feature column cannot enter the decomposition, if anything
+    fn column_problem(values: &[Option<f64>]) -> Option<&'static str> {
+        if values.iter().all(|v| v.is_none()) {
+            Some("column is entirely null")
+        } else if values.iter().flatten().any(|v| !v.is_finite()) {
+            Some("column contains non-finite values")
+        } else {
+            None
+        }
+    }
+
+    /// Split SURD terms by feature; a term belonging to a combination of features
+    /// is divided evenly among its members, so the shares add up to the totals.
+    /// Combination indices are positions in `agent_indices`, the columns passed to SURD.
+    pub fn surd_by_feature<T>(
+        result: &SurdResult<T>,
+        col_names: &[String],
+        agent_indices: &[usize],
+    ) -> BTreeMap<String, FeatureSurdTerms> {
+        let mut terms: BTreeMap<String, FeatureSurdTerms> = BTreeMap::new();
+        let mut credit = |combo: &[usize], value: f64, field: fn(&mut FeatureSurdTerms) -> &mut f64| {
+            if combo.is_empty() {
+                return;
+            }
+            let share = value / combo.len() as f64;
+            for name in combo.iter().filter_map(|&i| agent_indices.get(i).and_then(|&col| col_names.get(col))) {
+                *field(terms.entry(name.clone()).or_default()) += share;
+            }
+        };
+
+        for (combo, value) in result.redundant_info() {
+            credit(combo, *value, |t| &mut t.redundant);
+        }
+        for (combo, value) in result.mutual_info() {
+            credit(combo, *value, |t| &mut t.unique);
+        }
+        for (combo, value) in result.synergistic_info() {
+            credit(combo, *value, |t| &mut t.synergistic);
+        }
+        terms
+    }
+
     /// Aggregate SURD result into (Redundant, Unique, Synergistic) totals
-    fn aggregate_surd_result<T>(result: &SurdResult<T>) -> (f64, f64, f64) {
+    pub fn aggregate_surd_result<T>(result: &SurdResult<T>) -> (f64, f64, f64) {
         let redundant: f64 = result.redundant_info().values().sum();
         let unique: f64 = result.mutual_info().values().sum(); // mutual_info represents unique contribution
         let synergistic: f64 = result.synergistic_info().values().sum();
@@ -157,8 +1178,292 @@ mod tests {
             unique_info: 0.3,
             synergistic_info: 0.2,
             total_info: 1.0,
+            estimation_reliability: Some(0.9),
+            skipped: Vec::new(),
+            per_feature: BTreeMap::new(),
         };
         let json = serde_json::to_string(&result).unwrap();
         assert!(json.contains("redundant_info"));
     }
+
+    #[test]
+    fn test_combined_driver_score_weights() {
+        let synergy_heavy = SurdAnalysisResult {
+            redundant_info: 0.1,
+            unique_info: 0.1,
+            synergistic_info: 0.8,
+            total_info: 1.0,
+            estimation_reliability: None,
+            skipped: Vec::new(),
+            per_feature: BTreeMap::new(),
+        };
+
+        // Default weights reproduce the unique/total ratio
+        let default_score = synergy_heavy.combined_driver_score(&SurdScoreWeights::default());
+        assert!((default_score - 0.1).abs() < 1e-12);
+
+        let synergy_weights = SurdScoreWeights { redundant: 0.0, unique: 1.0, synergistic: 1.0 };
+        assert!(synergy_heavy.combined_driver_score(&synergy_weights) > default_score);
+    }
+
+    #[test]
+    fn test_estimation_reliability_flags_sparse_cells() -> Result<()> {
+        // Cells: (0, 0) x3, (0, 1) x3, (1, 1) x1 -> the lone sample is under-sampled
+        let df = df! [
+            "feature" => [0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0],
+            "target" => [0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0]
+        ]?;
+
+        let (tensor, _) = TensorAdapter::df_to_tensor(&df)?;
+
+        let reliability = CausalDiscovery::estimation_reliability(&tensor, 1, &[0], 3);
+        assert!((reliability - 6.0 / 7.0).abs() < 1e-12);
+
+        let reliability = CausalDiscovery::estimation_reliability(&tensor, 1, &[0], 1);
+        assert!((reliability - 1.0).abs() < 1e-12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_estimation_reliability_counts_joint_cells() -> Result<()> {
+        // Every (feature, target) pair is seen twice, but every (a, b, target) cell only once
+        let df = df! [
+            "a" => [0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0],
+            "b" => [0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0],
+            "target" => [0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0]
+        ]?;
+        let (tensor, _) = TensorAdapter::df_to_tensor(&df)?;
+
+        assert_eq!(CausalDiscovery::estimation_reliability(&tensor, 2, &[0], 2), 1.0);
+        assert_eq!(CausalDiscovery::estimation_reliability(&tensor, 2, &[1], 2), 1.0);
+        assert_eq!(CausalDiscovery::estimation_reliability(&tensor, 2, &[0, 1], 2), 0.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_surd_skips_problematic_columns() -> Result<()> {
+        let a: Vec<f64> = (0..200).map(|i| (i % 2) as f64).collect();
+        let b: Vec<f64> = (0..200).map(|i| ((i / 2) % 2) as f64).collect();
+        let target: Vec<f64> = a.iter().zip(&b).map(|(x, y)| ((*x as i32) ^ (*y as i32)) as f64).collect();
+        let broken: Vec<Option<f64>> = vec![None; 200];
+        let df = df! [
+            "Lactate" => a,
+            "Broken" => broken,
+            "MAP" => b,
+            "SepsisLabel" => target
+        ]?;
+
+        let options = SurdOptions::new().with_skip_failures(true);
+        let result = CausalDiscovery::run_surd(&df, "SepsisLabel", &options)?;
+        assert_eq!(result.skipped.len(), 1);
+        assert_eq!(result.skipped[0].features, vec!["Broken"]);
+        assert!(result.total_info > 0.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_surd_per_feature_maps_through_agent_indices() -> Result<()> {
+        // Target first and a skipped column in between, so positions differ from columns
+        let a: Vec<f64> = (0..200).map(|i| (i % 2) as f64).collect();
+        let b: Vec<f64> = (0..200).map(|i| ((i / 2) % 2) as f64).collect();
+        let df = df! [
+            "SepsisLabel" => a.clone(),
+            "Broken" => vec![None::<f64>; 200],
+            "MAP" => b,
+            "Lactate" => a
+        ]?;
+
+        let options = SurdOptions::new().with_skip_failures(true);
+        let result = CausalDiscovery::run_surd(&df, "SepsisLabel", &options)?;
+        assert!(!result.per_feature.contains_key("SepsisLabel"));
+        assert!(!result.per_feature.contains_key("Broken"));
+        // Lactate is the target, MAP is independent of it
+        let unique = |name: &str| result.per_feature.get(name).map_or(0.0, |t| t.unique);
+        assert!(unique("Lactate") > 0.5);
+        assert!(unique("MAP") < 0.1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_surd_pair_xor_is_synergistic() -> Result<()> {
+        // target = a XOR b: neither feature alone says anything about the target
+        let a: Vec<f64> = (0..200).map(|i| (i % 2) as f64).collect();
+        let b: Vec<f64> = (0..200).map(|i| ((i / 2) % 2) as f64).collect();
+        let target: Vec<f64> = a.iter().zip(&b).map(|(x, y)| ((*x as i32) ^ (*y as i32)) as f64).collect();
+        let df = df! [
+            "Lactate" => a,
+            "MAP" => b,
+            "SepsisLabel" => target
+        ]?;
+
+        let result = CausalDiscovery::run_surd_pair(&df, "SepsisLabel", "Lactate", "MAP", &SurdOptions::new())?;
+        assert!(result.synergistic_info > result.unique_info_a + result.unique_info_b + result.redundant_info);
+
+        assert!(CausalDiscovery::run_surd_pair(&df, "SepsisLabel", "Lactate", "SepsisLabel", &SurdOptions::new()).is_err());
+        assert!(CausalDiscovery::run_surd_pair(&df, "SepsisLabel", "Lactate", "Missing", &SurdOptions::new()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_run_mrmr_rejects_mismatched_column_names() -> Result<()> {
+        let df = df! [
+            "a" => [1.0, 2.0, 3.0],
+            "target" => [0.0, 1.0, 0.0]
+        ]?;
+        let (tensor, _) = TensorAdapter::df_to_tensor(&df)?;
+        let names = vec!["a".to_string(), "b".to_string(), "target".to_string()];
+
+        let err = CausalDiscovery::run_mrmr_tensor(&tensor, &names, "target", &MrmrOptions::new(1)).unwrap_err();
+        assert!(err.to_string().contains("3 column names for a tensor with 2 columns"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_target_is_target_not_found() -> Result<()> {
+        let df = df! [
+            "HR" => [80.0, 90.0, 100.0],
+            "MAP" => [70.0, 65.0, 60.0]
+        ]?;
+
+        let err = CausalDiscovery::run_mrmr(&df, "SepsisLabel", &MrmrOptions::new(1)).unwrap_err();
+        assert!(matches!(err, DeepCausalityError::TargetNotFound(ref col) if col == "SepsisLabel"));
+
+        let err = CausalDiscovery::run_surd(&df, "SepsisLabel", &SurdOptions::new()).unwrap_err();
+        assert!(matches!(err, DeepCausalityError::TargetNotFound(_)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_include_columns_restricts_candidates() -> Result<()> {
+        let df = df! [
+            "HR" => [80.0, 95.0, 110.0, 85.0, 120.0, 90.0],
+            "Lactate" => [1.0, 2.5, 4.0, 1.2, 4.5, 1.8],
+            "MAP" => [75.0, 70.0, 60.0, 72.0, 55.0, 68.0],
+            "SepsisLabel" => [0.0, 1.0, 1.0, 0.0, 1.0, 0.0]
+        ]?;
+
+        let options = MrmrOptions::new(3).with_include_columns(vec!["HR".to_string(), "MAP".to_string()]);
+        let selected = CausalDiscovery::run_mrmr(&df, "SepsisLabel", &options)?;
+        assert!(!selected.is_empty());
+        assert!(selected.iter().all(|(name, _)| name == "HR" || name == "MAP"));
+
+        let unknown = MrmrOptions::new(3).with_include_columns(vec!["Temp".to_string()]);
+        assert!(CausalDiscovery::run_mrmr(&df, "SepsisLabel", &unknown).is_err());
+
+        let both = options.with_exclude_columns(vec!["Lactate".to_string()]);
+        assert!(CausalDiscovery::run_mrmr(&df, "SepsisLabel", &both).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_condition_on_removes_covariates_from_pool() -> Result<()> {
+        let df = df! [
+            "Age" => [45.0, 60.0, 72.0, 38.0, 81.0, 55.0, 67.0, 50.0],
+            "HR" => [80.0, 95.0, 110.0, 85.0, 120.0, 90.0, 102.0, 88.0],
+            "Lactate" => [1.0, 2.5, 4.0, 1.2, 4.5, 1.8, 3.1, 1.5],
+            "SepsisLabel" => [0.0, 1.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0]
+        ]?;
+
+        let options = MrmrOptions::new(3).with_condition_on(vec!["Age".to_string()]);
+        let selected = CausalDiscovery::run_mrmr(&df, "SepsisLabel", &options)?;
+        assert!(!selected.is_empty());
+        assert!(selected.iter().all(|(name, _)| name != "Age"));
+
+        let on_target = MrmrOptions::new(3).with_condition_on(vec!["SepsisLabel".to_string()]);
+        assert!(CausalDiscovery::run_mrmr(&df, "SepsisLabel", &on_target).is_err());
+
+        let repeated = MrmrOptions::new(3).with_condition_on(vec!["Age".to_string(), "Age".to_string()]);
+        let err = CausalDiscovery::run_mrmr(&df, "SepsisLabel", &repeated).unwrap_err();
+        assert!(err.to_string().contains("more than once"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_normalize_scores_preserves_order() -> Result<()> {
+        let df = df! [
+            "HR" => [80.0, 95.0, 110.0, 85.0, 120.0, 90.0],
+            "Lactate" => [1.0, 2.5, 4.0, 1.2, 4.5, 1.8],
+            "MAP" => [75.0, 70.0, 60.0, 72.0, 55.0, 68.0],
+            "SepsisLabel" => [0.0, 1.0, 1.0, 0.0, 1.0, 0.0]
+        ]?;
+
+        let raw = CausalDiscovery::run_mrmr(&df, "SepsisLabel", &MrmrOptions::new(3))?;
+        let options = MrmrOptions::new(3).with_normalize_scores(true);
+        let normalized = CausalDiscovery::run_mrmr(&df, "SepsisLabel", &options)?;
+
+        let names = |features: &[(String, f64)]| features.iter().map(|(n, _)| n.clone()).collect::<Vec<_>>();
+        assert_eq!(names(&raw), names(&normalized));
+        let max = normalized.iter().map(|(_, s)| *s).fold(f64::NEG_INFINITY, f64::max);
+        assert!((max - 1.0).abs() < 1e-12);
+        assert!(normalized.iter().all(|(_, s)| *s <= 1.0));
+
+        // Non-positive scores have no meaningful scale and pass through
+        let flat = vec![("HR".to_string(), 0.0)];
+        assert_eq!(CausalDiscovery::normalize_scores(&flat), flat);
+        Ok(())
+    }
+
+    #[test]
+    fn test_empty_selection_is_reported() -> Result<()> {
+        let df = df! [
+            "HR" => [80.0, 95.0, 110.0, 85.0, 120.0, 90.0],
+            "MAP" => [75.0, 70.0, 60.0, 72.0, 55.0, 68.0],
+            "SepsisLabel" => [0.0, 1.0, 1.0, 0.0, 1.0, 0.0]
+        ]?;
+
+        let options = MrmrOptions::new(2).with_min_score(f64::MAX);
+        let selected = CausalDiscovery::run_mrmr(&df, "SepsisLabel", &options)?;
+        assert!(selected.is_empty());
+
+        let err = CausalDiscovery::ensure_selected(&selected, "SepsisLabel").unwrap_err();
+        assert!(matches!(err, DeepCausalityError::NoFeaturesSelected(_)));
+        assert!(err.to_string().contains("min_score"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_compare_rankings() {
+        let primary = vec![("HR".to_string(), 0.9), ("MAP".to_string(), 0.7), ("Temp".to_string(), 0.5)];
+        let compare = vec![("MAP".to_string(), 0.8), ("HR".to_string(), 0.6), ("Lactate".to_string(), 0.4)];
+
+        let comparison = CausalDiscovery::compare_rankings(&primary, &compare);
+        assert_eq!(comparison.only_primary, vec!["Temp"]);
+        assert_eq!(comparison.only_compare, vec!["Lactate"]);
+        assert!((comparison.feature_overlap - 0.5).abs() < 1e-12);
+
+        let hr = &comparison.changes[0];
+        assert_eq!((hr.primary_rank, hr.compare_rank), (Some(1), Some(2)));
+    }
+
+    #[test]
+    fn test_discretization_strategy_display_parses_back() {
+        for strategy in [
+            DiscretizationStrategy::EqualWidth,
+            DiscretizationStrategy::EqualFrequency,
+            DiscretizationStrategy::KMeans,
+        ] {
+            assert_eq!(strategy.to_string().parse::<DiscretizationStrategy>(), Ok(strategy));
+        }
+        assert_eq!(DiscretizationStrategy::KMeans.to_string(), "kmeans");
+    }
+
+    #[test]
+    fn test_equal_frequency_balances_skewed_column() {
+        // Exponentially skewed column: equal-width bins pile nearly everything into bin 0
+        let values: Vec<Option<f64>> = (0..100).map(|i| Some((i as f64 / 10.0).exp())).collect();
+
+        let count_bins = |binned: Vec<Option<f64>>| {
+            let mut counts = [0usize; 4];
+            for bin in binned.into_iter().flatten() {
+                counts[bin as usize] += 1;
+            }
+            counts
+        };
+
+        let width_counts = count_bins(CausalDiscovery::discretize(&values, 4, DiscretizationStrategy::EqualWidth));
+        let freq_counts = count_bins(CausalDiscovery::discretize(&values, 4, DiscretizationStrategy::EqualFrequency));
+
+        assert!(width_counts[0] > 80);
+        assert_eq!(freq_counts, [25, 25, 25, 25]);
+    }
 }
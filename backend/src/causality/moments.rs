@@ -0,0 +1,167 @@
+//! Correlation moments accumulated one row chunk at a time
+//!
+//! mRMR only needs pairwise Pearson correlations, and those merge exactly from
+//! per-chunk moments. Feeding chunks into [`CorrelationMoments`] lets selection
+//! run on data too large to hold in memory at once.
+
+use crate::error::{DeepCausalityError, Result};
+use deep_causality_tensor::CausalTensor;
+use rayon::prelude::*;
+
+/// Moments of one column pair over the rows where both are present
+#[derive(Debug, Clone, Copy, Default)]
+struct PairMoments {
+    n: usize,
+    mean_i: f64,
+    mean_j: f64,
+    /// Summed squared deviations from the means
+    m2_i: f64,
+    m2_j: f64,
+    /// Summed products of the two columns' deviations
+    c: f64,
+}
+
+impl PairMoments {
+    /// Two-pass moments of one chunk, which stay accurate for large, offset values
+    fn from_rows(a: &[Option<f64>], b: &[Option<f64>]) -> Self {
+        let pairs = || a.iter().zip(b).filter_map(|(x, y)| Some(((*x)?, (*y)?)));
+        let (n, sum_i, sum_j) = pairs().fold((0, 0.0, 0.0), |(n, si, sj), (x, y)| (n + 1, si + x, sj + y));
+        if n == 0 {
+            return Self::default();
+        }
+
+        let (mean_i, mean_j) = (sum_i / n as f64, sum_j / n as f64);
+        let (mut m2_i, mut m2_j, mut c) = (0.0, 0.0, 0.0);
+        for (x, y) in pairs() {
+            let (dx, dy) = (x - mean_i, y - mean_j);
+            m2_i += dx * dx;
+            m2_j += dy * dy;
+            c += dx * dy;
+        }
+        Self { n, mean_i, mean_j, m2_i, m2_j, c }
+    }
+
+    /// Fold in another chunk's moments (Chan et al.'s pairwise update)
+    fn merge(&mut self, other: &Self) {
+        if other.n == 0 {
+            return;
+        }
+        if self.n == 0 {
+            *self = *other;
+            return;
+        }
+
+        let n = (self.n + other.n) as f64;
+        let weight = self.n as f64 * other.n as f64 / n;
+        let (d_i, d_j) = (other.mean_i - self.mean_i, other.mean_j - self.mean_j);
+        self.mean_i += d_i * other.n as f64 / n;
+        self.mean_j += d_j * other.n as f64 / n;
+        self.m2_i += other.m2_i + d_i * d_i * weight;
+        self.m2_j += other.m2_j + d_j * d_j * weight;
+        self.c += other.c + d_i * d_j * weight;
+        self.n += other.n;
+    }
+}
+
+/// Pairwise correlation moments of every column pair, grown one row chunk at a time
+#[derive(Debug, Clone)]
+pub struct CorrelationMoments {
+    n_cols: usize,
+    n_rows: usize,
+    /// Upper triangle (i < j), row by row
+    pairs: Vec<PairMoments>,
+}
+
+impl CorrelationMoments {
+    pub fn new(n_cols: usize) -> Self {
+        Self {
+            n_cols,
+            n_rows: 0,
+            pairs: vec![PairMoments::default(); n_cols * n_cols.saturating_sub(1) / 2],
+        }
+    }
+
+    pub fn n_cols(&self) -> usize {
+        self.n_cols
+    }
+
+    /// Rows added so far, including ones with missing values
+    pub fn n_rows(&self) -> usize {
+        self.n_rows
+    }
+
+    /// Add a chunk of rows, given as a column-major tensor with the same columns
+    pub fn update(&mut self, chunk: &CausalTensor<Option<f64>>) -> Result<()> {
+        let [height, width] = *chunk.shape() else {
+            return Err(DeepCausalityError::Mrmr("Expected a 2D chunk".into()));
+        };
+        if width != self.n_cols {
+            return Err(DeepCausalityError::Mrmr(format!(
+                "Chunk has {} columns, expected {}", width, self.n_cols
+            )));
+        }
+        if height == 0 {
+            return Ok(());
+        }
+
+        let columns: Vec<&[Option<f64>]> = chunk.as_slice().chunks(height).collect();
+        let n = self.n_cols;
+        let pair_indices: Vec<(usize, usize)> = (0..n).flat_map(|i| (i + 1..n).map(move |j| (i, j))).collect();
+        self.pairs.par_iter_mut()
+            .zip(pair_indices.par_iter())
+            .for_each(|(moments, &(i, j))| moments.merge(&PairMoments::from_rows(columns[i], columns[j])));
+        self.n_rows += height;
+        Ok(())
+    }
+
+    /// Pearson correlation of columns `i` and `j` over the rows where both were present,
+    /// with that row count
+    pub fn correlation(&self, i: usize, j: usize) -> (f64, usize) {
+        if i == j {
+            return (1.0, self.n_rows);
+        }
+        let (i, j) = (i.min(j), i.max(j));
+        let pair = &self.pairs[i * (2 * self.n_cols - i - 1) / 2 + (j - i - 1)];
+
+        // Constant columns carry no linear signal
+        if pair.n < 2 || pair.m2_i <= 0.0 || pair.m2_j <= 0.0 {
+            return (0.0, pair.n);
+        }
+        (pair.c / (pair.m2_i * pair.m2_j).sqrt(), pair.n)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    #[test]
+    fn test_chunked_moments_match_single_pass() -> Result<()> {
+        // Large offsets would lose precision with naive running sums of squares
+        let x: Vec<Option<f64>> = (0..90).map(|i| Some(1e6 + (i % 7) as f64)).collect();
+        let y: Vec<Option<f64>> = (0..90).map(|i| if i % 10 == 3 { None } else { Some(1e6 + ((i * 3) % 11) as f64) }).collect();
+        let z: Vec<Option<f64>> = x.iter().map(|v| v.map(|v| 2.0 * v + 1.0)).collect();
+        let tensor = |rows: std::ops::Range<usize>| {
+            let data = [&x, &y, &z].iter().flat_map(|c| c[rows.clone()].iter().copied()).collect();
+            CausalTensor::new(data, vec![rows.len(), 3])
+        };
+
+        let mut whole = CorrelationMoments::new(3);
+        whole.update(&tensor(0..90)?)?;
+        let mut chunked = CorrelationMoments::new(3);
+        for start in (0..90).step_by(25) {
+            chunked.update(&tensor(start..(start + 25).min(90))?)?;
+        }
+
+        assert_eq!(chunked.n_rows(), 90);
+        assert!((chunked.correlation(0, 2).0 - 1.0).abs() < 1e-9);
+        for (i, j) in [(0, 1), (1, 2), (2, 0)] {
+            let (a, b) = (whole.correlation(i, j), chunked.correlation(i, j));
+            assert_eq!(a.1, b.1);
+            assert!((a.0 - b.0).abs() < 1e-9);
+        }
+        assert_eq!(chunked.correlation(0, 1).1, 81);
+        Ok(())
+    }
+}
@@ -0,0 +1,1168 @@
+//! Native greedy mRMR selector
+//!
+//! Mirrors the FCQ formulation used by `mrmr_features_selector` (F-statistic
+//! relevance divided by correlation redundancy) for selection modes the
+//! upstream selector does not offer, and adds the FCD difference variant.
+
+use super::moments::CorrelationMoments;
+use super::{MrmrOptions, MrmrScheme, RedundancyMode};
+use crate::error::{DeepCausalityError, Result};
+use deep_causality_tensor::CausalTensor;
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use std::collections::HashMap;
+
+/// Redundancy floor so uncorrelated candidates don't produce infinite scores
+const MIN_REDUNDANCY: f64 = 1e-3;
+
+/// Why a candidate column was not selected
+#[derive(Debug, Clone)]
+pub(crate) struct Rejection {
+    pub index: usize,
+    /// F-statistic against the target
+    pub relevance: f64,
+    /// Highest absolute correlation with a feature selected by the time it was dropped
+    pub max_redundancy: f64,
+    /// The selected column behind `max_redundancy`
+    pub blocked_by: Option<usize>,
+    /// 1-based selection round: the one that selected a group member when `grouped_out`,
+    /// otherwise the last round the candidate was still in the pool
+    pub round: usize,
+    /// Removed because a member of its feature group was selected
+    pub grouped_out: bool,
+}
+
+/// How one candidate scored in a selection round
+#[derive(Debug, Clone)]
+pub(crate) struct CandidateScore {
+    pub index: usize,
+    /// F-statistic against the target
+    pub relevance: f64,
+    /// Redundancy with the features selected before this round (0 in the first round)
+    pub redundancy: f64,
+    pub score: f64,
+}
+
+/// One selection round: the chosen column and how every candidate in the pool scored
+#[derive(Debug, Clone)]
+pub(crate) struct Step {
+    pub chosen: usize,
+    pub candidates: Vec<CandidateScore>,
+}
+
+/// Selected (column index, score) pairs in order, plus the rejected candidates
+pub(crate) type Selection = (Vec<(usize, f64)>, Vec<Rejection>);
+
+/// Called after every selection round with (features selected so far, percent complete);
+/// returning an error aborts the selection with that error
+pub type ProgressHook<'a> = &'a mut dyn FnMut(usize, f64) -> Result<()>;
+
+/// Greedily select up to `options.max_features` columns, returning (column index, score)
+/// in selection order. Selecting any member of a group removes the rest of that group,
+/// and selection stops early once the best candidate falls below `options.min_score`.
+pub(crate) fn select_features(
+    tensor: &CausalTensor<Option<f64>>,
+    target_idx: usize,
+    options: &MrmrOptions,
+    groups: &[Vec<usize>],
+) -> Result<Vec<(usize, f64)>> {
+    let mut cache = ColumnCache::new(tensor, options.n_threads, options.sample_weights.as_deref())?;
+    greedy_select(&mut cache, target_idx, &[], options, groups, false, None, &mut |_, _| Ok(()))
+        .map(|(selected, _)| selected)
+}
+
+/// Like [`select_features`], reporting progress after every round and recording every
+/// candidate's score in every round
+pub(crate) fn select_with_trace(
+    tensor: &CausalTensor<Option<f64>>,
+    target_idx: usize,
+    options: &MrmrOptions,
+    groups: &[Vec<usize>],
+    on_progress: ProgressHook,
+) -> Result<Vec<Step>> {
+    let mut cache = ColumnCache::new(tensor, options.n_threads, options.sample_weights.as_deref())?;
+    let mut steps = Vec::new();
+    greedy_select(&mut cache, target_idx, &[], options, groups, false, Some(&mut steps), on_progress)?;
+    Ok(steps)
+}
+
+/// Like [`select_with_trace`] for several targets over one tensor. Column statistics and
+/// feature correlations are shared between the runs, and no target is ever a candidate
+/// for another. Progress percentages cover all targets.
+pub(crate) fn select_multi_target_with_trace(
+    tensor: &CausalTensor<Option<f64>>,
+    target_indices: &[usize],
+    options: &MrmrOptions,
+    groups: &[Vec<usize>],
+    on_progress: ProgressHook,
+) -> Result<Vec<Vec<Step>>> {
+    let mut cache = ColumnCache::new(tensor, options.n_threads, options.sample_weights.as_deref())?;
+    trace_targets(&mut cache, target_indices, &[], options, groups, on_progress)
+}
+
+/// Like [`select_multi_target_with_trace`], from correlation moments accumulated over row
+/// chunks; columns in `excluded` are never candidates
+pub(crate) fn select_multi_target_from_moments(
+    moments: &CorrelationMoments,
+    target_indices: &[usize],
+    excluded: &[usize],
+    options: &MrmrOptions,
+    groups: &[Vec<usize>],
+    on_progress: ProgressHook,
+) -> Result<Vec<Vec<Step>>> {
+    let mut cache = ColumnCache::from_moments(moments, options.n_threads)?;
+    trace_targets(&mut cache, target_indices, excluded, options, groups, on_progress)
+}
+
+/// Traced greedy selection for each target in turn over one shared cache
+fn trace_targets(
+    cache: &mut ColumnCache,
+    target_indices: &[usize],
+    excluded: &[usize],
+    options: &MrmrOptions,
+    groups: &[Vec<usize>],
+    on_progress: ProgressHook,
+) -> Result<Vec<Vec<Step>>> {
+    let excluded: Vec<usize> = target_indices.iter().chain(excluded).copied().collect();
+    let n_targets = target_indices.len() as f64;
+    target_indices.iter()
+        .enumerate()
+        .map(|(t, &target_idx)| {
+            let mut steps = Vec::new();
+            greedy_select(
+                cache, target_idx, &excluded, options, groups, false, Some(&mut steps),
+                &mut |selected, percent| on_progress(selected, (t as f64 * 100.0 + percent) / n_targets),
+            )?;
+            Ok(steps)
+        })
+        .collect()
+}
+
+/// Like [`select_features`], also explaining every candidate that was left out
+pub(crate) fn select_with_rejections(
+    tensor: &CausalTensor<Option<f64>>,
+    target_idx: usize,
+    options: &MrmrOptions,
+    groups: &[Vec<usize>],
+) -> Result<Selection> {
+    let mut cache = ColumnCache::new(tensor, options.n_threads, options.sample_weights.as_deref())?;
+    greedy_select(&mut cache, target_idx, &[], options, groups, true, None, &mut |_, _| Ok(()))
+}
+
+/// Where correlations come from: column slices of one tensor, or moments accumulated
+/// from row chunks
+enum Source<'a> {
+    Columns {
+        columns: Vec<&'a [Option<f64>]>,
+        stats: Vec<ColumnStats>,
+        weights: Option<&'a [f64]>,
+    },
+    Moments(&'a CorrelationMoments),
+}
+
+impl Source<'_> {
+    fn n_cols(&self) -> usize {
+        match self {
+            Source::Columns { columns, .. } => columns.len(),
+            Source::Moments(moments) => moments.n_cols(),
+        }
+    }
+
+    fn correlation(&self, i: usize, j: usize) -> (f64, usize) {
+        match self {
+            Source::Columns { columns, weights: Some(weights), .. } => weighted_pearson(columns[i], columns[j], weights),
+            Source::Columns { columns, stats, weights: None } => correlation(columns, stats, i, j),
+            Source::Moments(moments) => moments.correlation(i, j),
+        }
+    }
+}
+
+/// Correlation source of one dataset. Correlations are memoized, so several
+/// selections over the same data (one per target) compute each column pair once.
+struct ColumnCache<'a> {
+    source: Source<'a>,
+    correlations: HashMap<(usize, usize), (f64, usize)>,
+    /// Worker threads for per-candidate work; None runs everything on the calling thread
+    pool: Option<ThreadPool>,
+}
+
+impl<'a> ColumnCache<'a> {
+    fn new(tensor: &'a CausalTensor<Option<f64>>, n_threads: Option<usize>, weights: Option<&'a [f64]>) -> Result<Self> {
+        let height = tensor.shape()[0];
+        if height == 0 {
+            return Err(DeepCausalityError::Mrmr("Cannot run mRMR on an empty tensor".into()));
+        }
+        if let Some(weights) = weights {
+            if weights.len() != height {
+                return Err(DeepCausalityError::Mrmr(format!(
+                    "Got {} sample weights for {} rows", weights.len(), height
+                )));
+            }
+            if weights.iter().any(|w| !w.is_finite() || *w < 0.0) || !weights.iter().any(|w| *w > 0.0) {
+                return Err(DeepCausalityError::Mrmr(
+                    "Sample weights must be finite, non-negative and not all zero".into()
+                ));
+            }
+        }
+
+        // Column-major layout: column j occupies rows j*height..(j+1)*height
+        let columns: Vec<&[Option<f64>]> = tensor.as_slice().chunks(height).collect();
+        let stats = columns.iter().map(|column| ColumnStats::new(column)).collect();
+        Self::with_source(Source::Columns { columns, stats, weights }, n_threads)
+    }
+
+    fn from_moments(moments: &'a CorrelationMoments, n_threads: Option<usize>) -> Result<Self> {
+        if moments.n_rows() == 0 {
+            return Err(DeepCausalityError::Mrmr("Cannot run mRMR before any rows were added".into()));
+        }
+        Self::with_source(Source::Moments(moments), n_threads)
+    }
+
+    fn with_source(source: Source<'a>, n_threads: Option<usize>) -> Result<Self> {
+        // Only the scoring runs on the pool; the selection loop (and its progress hook)
+        // stays on the caller's thread
+        let pool = n_threads
+            .map(|n| ThreadPoolBuilder::new().num_threads(n).build())
+            .transpose()
+            .map_err(|e| DeepCausalityError::Mrmr(format!("Failed to start mRMR thread pool: {}", e)))?;
+        Ok(Self { source, correlations: HashMap::new(), pool })
+    }
+
+    /// `f` applied to every item, on the pool when there is one
+    fn map<T: Send>(&self, items: &[usize], f: impl Fn(usize) -> T + Sync + Send) -> Vec<T> {
+        match &self.pool {
+            Some(pool) => pool.install(|| items.par_iter().map(|&i| f(i)).collect()),
+            None => items.iter().map(|&i| f(i)).collect(),
+        }
+    }
+
+    /// Correlation and pair count of columns `i` and `j`, which is symmetric
+    fn correlation(&mut self, i: usize, j: usize) -> (f64, usize) {
+        let source = &self.source;
+        *self.correlations
+            .entry((i.min(j), i.max(j)))
+            .or_insert_with(|| source.correlation(i, j))
+    }
+
+    /// Correlations of every column in `others` with column `j`, computing the
+    /// ones not cached yet in one (possibly parallel) batch
+    fn correlations_with(&mut self, others: &[usize], j: usize) -> Vec<(f64, usize)> {
+        let key = |i: usize| (i.min(j), i.max(j));
+        let missing: Vec<usize> = others.iter()
+            .copied()
+            .filter(|&i| !self.correlations.contains_key(&key(i)))
+            .collect();
+        let source = &self.source;
+        let computed = self.map(&missing, |i| source.correlation(i, j));
+        self.correlations.extend(missing.into_iter().map(key).zip(computed));
+        others.iter().map(|&i| self.correlations[&key(i)]).collect()
+    }
+}
+
+/// Greedy rounds for one target; columns in `excluded` (and the target) are never candidates
+#[allow(clippy::too_many_arguments)]
+fn greedy_select(
+    cache: &mut ColumnCache,
+    target_idx: usize,
+    excluded: &[usize],
+    options: &MrmrOptions,
+    groups: &[Vec<usize>],
+    explain: bool,
+    mut trace: Option<&mut Vec<Step>>,
+    on_progress: ProgressHook,
+) -> Result<Selection> {
+    let n_cols = cache.source.n_cols();
+    if target_idx >= n_cols {
+        return Err(DeepCausalityError::Mrmr(format!("Target index {} out of range", target_idx)));
+    }
+
+    let all_columns: Vec<usize> = (0..n_cols).collect();
+    let relevance: Vec<f64> = cache.correlations_with(&all_columns, target_idx)
+        .into_iter()
+        .map(|(r, n)| f_statistic(r, n))
+        .collect();
+
+    // Only correlations against already-selected features are ever needed, so they are
+    // computed one selected column per round instead of as a dense n_cols x n_cols matrix
+    let mut selected_corr: Vec<Vec<f64>> = vec![Vec::new(); n_cols];
+    let mut among_selected: Vec<Vec<f64>> = Vec::with_capacity(options.max_features);
+
+    let mut remaining: Vec<usize> = (0..n_cols).filter(|&i| i != target_idx && !excluded.contains(&i)).collect();
+    let mut selected: Vec<(usize, f64)> = Vec::with_capacity(options.max_features);
+    // (candidate, round, grouped out) for every column a group member's selection removes
+    let mut dropped: Vec<(usize, usize, bool)> = Vec::new();
+    let mut round = 0;
+
+    while selected.len() < options.max_features && !remaining.is_empty() {
+        round += 1;
+        let scores: Vec<CandidateScore> = cache.map(&remaining, |candidate| {
+            if selected.is_empty() {
+                let relevance = relevance[candidate];
+                return CandidateScore { index: candidate, relevance, redundancy: 0.0, score: relevance };
+            }
+            let redundancy = redundancy(&selected_corr[candidate], &among_selected, options.redundancy);
+            let score = match options.scheme {
+                MrmrScheme::Quotient => relevance[candidate] / redundancy.max(MIN_REDUNDANCY),
+                MrmrScheme::Difference => relevance[candidate] - redundancy,
+            };
+            CandidateScore { index: candidate, relevance: relevance[candidate], redundancy, score }
+        });
+
+        // Ties go to the earliest remaining column
+        let (pos, score) = scores.iter()
+            .enumerate()
+            .map(|(pos, candidate)| (pos, candidate.score))
+            .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)))
+            .expect("remaining candidates are non-empty");
+
+        if !options.accepts(score) {
+            break;
+        }
+        if let Some(steps) = trace.as_deref_mut() {
+            steps.push(Step { chosen: remaining[pos], candidates: scores });
+        }
+
+        let chosen = remaining.remove(pos);
+        if let Some(group) = groups.iter().find(|g| g.contains(&chosen)) {
+            remaining.retain(|&c| {
+                let keep = !group.contains(&c);
+                if !keep {
+                    dropped.push((c, round, true));
+                }
+                keep
+            });
+        }
+        selected.push((chosen, score));
+
+        // Grow the selected-set correlation matrix by the new member
+        let mut row = std::mem::take(&mut selected_corr[chosen]);
+        for (existing, &r) in among_selected.iter_mut().zip(&row) {
+            existing.push(r);
+        }
+        row.push(1.0);
+        among_selected.push(row);
+
+        if selected.len() < options.max_features {
+            let correlations = cache.correlations_with(&remaining, chosen);
+            for (&candidate, (r, _)) in remaining.iter().zip(correlations) {
+                selected_corr[candidate].push(r);
+            }
+        }
+        on_progress(selected.len(), 100.0 * selected.len() as f64 / options.max_features as f64)?;
+    }
+
+    // Stopping early (min_score, exhausted pool) still finishes the run
+    if selected.len() < options.max_features {
+        on_progress(selected.len(), 100.0)?;
+    }
+
+    if !explain {
+        return Ok((selected, Vec::new()));
+    }
+
+    // Candidates that were never grouped out stay in the pool through the last round
+    dropped.extend(remaining.iter().map(|&c| (c, round, false)));
+    let rejections = dropped.into_iter()
+        .map(|(index, round, grouped_out)| {
+            // Only the features selected by the time the candidate dropped out could block it
+            let (blocked_by, max_redundancy) = selected[..round.min(selected.len())].iter()
+                .map(|&(s, _)| (Some(s), cache.correlation(index, s).0.abs()))
+                .fold((None, 0.0), |best, next| if next.1 > best.1 { next } else { best });
+            Rejection { index, relevance: relevance[index], max_redundancy, blocked_by, round, grouped_out }
+        })
+        .collect();
+
+    Ok((selected, rejections))
+}
+
+/// Relevance of every column in an existing selection and its redundancy with the
+/// columns selected before it, measured as the native selector would; scores are kept
+pub(crate) fn describe_selection(
+    tensor: &CausalTensor<Option<f64>>,
+    target_idx: usize,
+    selected: &[(usize, f64)],
+    options: &MrmrOptions,
+) -> Result<Vec<CandidateScore>> {
+    let mut cache = ColumnCache::new(tensor, None, options.sample_weights.as_deref())?;
+    let mut among_selected: Vec<Vec<f64>> = Vec::with_capacity(selected.len());
+    let mut described = Vec::with_capacity(selected.len());
+    for (k, &(index, score)) in selected.iter().enumerate() {
+        let (r, n) = cache.correlation(index, target_idx);
+        let previous: Vec<usize> = selected[..k].iter().map(|&(s, _)| s).collect();
+        let r_cs: Vec<f64> = cache.correlations_with(&previous, index).into_iter().map(|(r, _)| r).collect();
+        let redundancy = if k == 0 { 0.0 } else { redundancy(&r_cs, &among_selected, options.redundancy) };
+
+        for (existing, &r) in among_selected.iter_mut().zip(&r_cs) {
+            existing.push(r);
+        }
+        let mut row = r_cs;
+        row.push(1.0);
+        among_selected.push(row);
+        described.push(CandidateScore { index, relevance: f_statistic(r, n), redundancy, score });
+    }
+    Ok(described)
+}
+
+/// Gaussian mutual information in bits between every pair of columns, -log2(1 - r^2) / 2
+/// for the Pearson correlation r that mRMR scores relevance and redundancy with. Exact
+/// for jointly normal columns and a lower bound otherwise; the diagonal is NaN.
+pub(crate) fn information_matrix(tensor: &CausalTensor<Option<f64>>, n_threads: Option<usize>) -> Result<Vec<Vec<f64>>> {
+    let cache = ColumnCache::new(tensor, n_threads, None)?;
+    let n = cache.source.n_cols();
+    let pairs: Vec<(usize, usize)> = (0..n).flat_map(|i| (i + 1..n).map(move |j| (i, j))).collect();
+    let source = &cache.source;
+    let information = cache.map(&(0..pairs.len()).collect::<Vec<_>>(), |p| {
+        let (r, _) = source.correlation(pairs[p].0, pairs[p].1);
+        // Same cap as the F-statistic, so perfectly correlated columns stay finite
+        -0.5 * (1.0 - r * r).max(1e-12).log2()
+    });
+
+    let mut matrix = vec![vec![f64::NAN; n]; n];
+    for (&(i, j), value) in pairs.iter().zip(information) {
+        matrix[i][j] = value;
+        matrix[j][i] = value;
+    }
+    Ok(matrix)
+}
+
+/// Replace every column except the `covariates` with its residual from a least-squares
+/// fit (with intercept) on them, so correlations on the result are partial correlations.
+/// Rows where the value or any covariate is missing become missing; covariates are copied.
+pub(crate) fn residualize(
+    tensor: &CausalTensor<Option<f64>>,
+    covariates: &[usize],
+    col_names: &[String],
+) -> Result<CausalTensor<Option<f64>>> {
+    let height = tensor.shape()[0];
+    let columns: Vec<&[Option<f64>]> = tensor.as_slice().chunks(height.max(1)).collect();
+
+    // Design row per sample: intercept then covariates, None if any covariate is missing
+    let design: Vec<Option<Vec<f64>>> = (0..height)
+        .map(|row| std::iter::once(Some(1.0)).chain(covariates.iter().map(|&c| columns[c][row])).collect())
+        .collect();
+    let k = covariates.len() + 1;
+
+    let mut data = Vec::with_capacity(tensor.as_slice().len());
+    for (j, column) in columns.iter().enumerate() {
+        if covariates.contains(&j) {
+            data.extend_from_slice(column);
+            continue;
+        }
+
+        // Normal equations over complete rows
+        let (mut xtx, mut xty, mut n) = (vec![vec![0.0; k]; k], vec![0.0; k], 0);
+        for (x, y) in design.iter().zip(column.iter()) {
+            let (Some(x), Some(y)) = (x, y) else { continue };
+            n += 1;
+            for ((row, t), &xa) in xtx.iter_mut().zip(xty.iter_mut()).zip(x) {
+                *t += xa * y;
+                for (cell, &xb) in row.iter_mut().zip(x) {
+                    *cell += xa * xb;
+                }
+            }
+        }
+        let beta = if n > k { solve(xtx, xty) } else { None }.ok_or_else(|| DeepCausalityError::Mrmr(format!(
+            "Cannot condition {} on the covariates: too few complete rows or collinear covariates", col_names[j]
+        )))?;
+
+        data.extend(design.iter().zip(column.iter()).map(|(x, y)| {
+            let (x, y) = (x.as_ref()?, (*y)?);
+            Some(y - x.iter().zip(&beta).map(|(a, b)| a * b).sum::<f64>())
+        }));
+    }
+
+    CausalTensor::new(data, tensor.shape().to_vec())
+        .map_err(|e| DeepCausalityError::TensorConversion(format!("Failed to build residual CausalTensor: {}", e)))
+}
+
+/// Redundancy of a candidate given its correlations with the selected columns (`r_cs`)
+/// and the correlations among the selected columns themselves (`r_ss`)
+fn redundancy(r_cs: &[f64], r_ss: &[Vec<f64>], mode: RedundancyMode) -> f64 {
+    match mode {
+        RedundancyMode::Pairwise => r_cs.iter().map(|r| r.abs()).sum::<f64>() / r_cs.len() as f64,
+        RedundancyMode::PartialCorrelation => multiple_correlation(r_cs, r_ss),
+    }
+}
+
+/// Multiple correlation of a candidate on the selected set.
+///
+/// Equivalent to chaining partial correlations, 1 - R² = Π (1 - r²(c, s_k | s_1..s_k-1)),
+/// so variance the selected features share is only counted once.
+fn multiple_correlation(r_cs: &[f64], r_ss: &[Vec<f64>]) -> f64 {
+    match solve(r_ss.to_vec(), r_cs.to_vec()) {
+        Some(beta) => beta.iter().zip(r_cs).map(|(b, r)| b * r).sum::<f64>().clamp(0.0, 1.0).sqrt(),
+        // Collinear selected set: fall back to the strongest single correlation
+        None => r_cs.iter().fold(0.0, |max: f64, r| max.max(r.abs())),
+    }
+}
+
+/// Per-column summary computed once so complete columns correlate in a single pass
+struct ColumnStats {
+    mean: f64,
+    /// Square root of the summed squared deviations from the mean
+    norm: f64,
+    /// No nulls, so every row pairs with every other complete column
+    complete: bool,
+}
+
+impl ColumnStats {
+    fn new(column: &[Option<f64>]) -> Self {
+        let (sum, n) = column.iter().flatten().fold((0.0, 0), |(sum, n), v| (sum + v, n + 1));
+        let mean = if n > 0 { sum / n as f64 } else { 0.0 };
+        let norm = column.iter().flatten().map(|v| (v - mean).powi(2)).sum::<f64>().sqrt();
+        Self { mean, norm, complete: n == column.len() }
+    }
+}
+
+/// Pearson correlation of two columns with the pair count, using the cached stats
+/// when neither column has nulls and pairwise-complete rows otherwise
+fn correlation(columns: &[&[Option<f64>]], stats: &[ColumnStats], i: usize, j: usize) -> (f64, usize) {
+    let (a, b) = (&stats[i], &stats[j]);
+    if !(a.complete && b.complete) {
+        return pearson(columns[i], columns[j]);
+    }
+
+    let n = columns[i].len();
+    // Constant columns carry no linear signal
+    if n < 2 || a.norm <= 0.0 || b.norm <= 0.0 {
+        return (0.0, n);
+    }
+    let cov: f64 = columns[i].iter().flatten()
+        .zip(columns[j].iter().flatten())
+        .map(|(x, y)| (x - a.mean) * (y - b.mean))
+        .sum();
+    (cov / (a.norm * b.norm), n)
+}
+
+/// Solve `a x = b` by Gaussian elimination with partial pivoting
+fn solve(mut a: Vec<Vec<f64>>, mut b: Vec<f64>) -> Option<Vec<f64>> {
+    let n = b.len();
+    for col in 0..n {
+        let pivot = (col..n).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-10 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+
+        let (upper, lower) = a.split_at_mut(col + 1);
+        let pivot_row = &upper[col];
+        for (offset, row) in lower.iter_mut().enumerate() {
+            let factor = row[col] / pivot_row[col];
+            for (x, p) in row[col..].iter_mut().zip(&pivot_row[col..]) {
+                *x -= factor * p;
+            }
+            b[col + 1 + offset] -= factor * b[col];
+        }
+    }
+
+    let mut x = vec![0.0; n];
+    for row in (0..n).rev() {
+        let sum: f64 = ((row + 1)..n).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - sum) / a[row][row];
+    }
+    Some(x)
+}
+
+/// Pearson correlation over rows where both columns are present, with the pair count
+fn pearson(x: &[Option<f64>], y: &[Option<f64>]) -> (f64, usize) {
+    let pairs: Vec<(f64, f64)> = x.iter()
+        .zip(y)
+        .filter_map(|(a, b)| Some(((*a)?, (*b)?)))
+        .collect();
+    let n = pairs.len();
+    if n < 2 {
+        return (0.0, n);
+    }
+
+    let mean_x = pairs.iter().map(|p| p.0).sum::<f64>() / n as f64;
+    let mean_y = pairs.iter().map(|p| p.1).sum::<f64>() / n as f64;
+    let (mut cov, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for (a, b) in &pairs {
+        cov += (a - mean_x) * (b - mean_y);
+        var_x += (a - mean_x).powi(2);
+        var_y += (b - mean_y).powi(2);
+    }
+
+    // Constant columns carry no linear signal
+    if var_x <= 0.0 || var_y <= 0.0 {
+        return (0.0, n);
+    }
+    (cov / (var_x * var_y).sqrt(), n)
+}
+
+/// Weighted Pearson correlation over rows where both columns are present, with the
+/// Kish effective sample size (sum w)^2 / sum w^2 as the pair count
+fn weighted_pearson(x: &[Option<f64>], y: &[Option<f64>], weights: &[f64]) -> (f64, usize) {
+    let triples: Vec<(f64, f64, f64)> = x.iter()
+        .zip(y)
+        .zip(weights)
+        .filter_map(|((a, b), &w)| Some(((*a)?, (*b)?, w)))
+        .filter(|&(_, _, w)| w > 0.0)
+        .collect();
+    if triples.len() < 2 {
+        return (0.0, triples.len());
+    }
+    let (sum_w, sum_w2) = triples.iter().fold((0.0, 0.0), |(s, s2), &(_, _, w)| (s + w, s2 + w * w));
+    let n = (sum_w * sum_w / sum_w2).round() as usize;
+
+    let mean_x = triples.iter().map(|&(a, _, w)| w * a).sum::<f64>() / sum_w;
+    let mean_y = triples.iter().map(|&(_, b, w)| w * b).sum::<f64>() / sum_w;
+    let (mut cov, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+    for &(a, b, w) in &triples {
+        cov += w * (a - mean_x) * (b - mean_y);
+        var_x += w * (a - mean_x).powi(2);
+        var_y += w * (b - mean_y).powi(2);
+    }
+
+    // Constant columns carry no linear signal
+    if var_x <= 0.0 || var_y <= 0.0 {
+        return (0.0, n);
+    }
+    (cov / (var_x * var_y).sqrt(), n)
+}
+
+/// F-statistic of a simple linear fit with correlation `r` over `n` samples
+fn f_statistic(r: f64, n: usize) -> f64 {
+    if n <= 2 {
+        return 0.0;
+    }
+    let r2 = r * r;
+    r2 * (n - 2) as f64 / (1.0 - r2).max(1e-12)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    /// Deterministic uniform noise in [-1, 1)
+    fn noise(seed: u64, n: usize) -> Vec<f64> {
+        let mut state = seed;
+        (0..n)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+                (state >> 11) as f64 / (1u64 << 53) as f64 * 2.0 - 1.0
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_partial_correlation_picks_more_diverse_set() -> Result<()> {
+        let n = 200;
+        let a = noise(1, n);
+        let d = noise(4, n);
+        let (u2, u3, u5, u6) = (noise(2, n), noise(3, n), noise(5, n), noise(6, n));
+
+        // a, b, c are near-copies; e mixes a and d; the target is driven by a and d
+        let b: Vec<f64> = (0..n).map(|i| a[i] + 0.05 * u2[i]).collect();
+        let c: Vec<f64> = (0..n).map(|i| a[i] + 0.05 * u3[i]).collect();
+        let e: Vec<f64> = (0..n).map(|i| 0.5 * a[i] + 0.5 * d[i] + 0.8 * u5[i]).collect();
+        let y: Vec<f64> = (0..n).map(|i| a[i] + d[i] + 0.3 * u6[i]).collect();
+
+        let flat: Vec<Option<f64>> = [&a, &b, &c, &d, &e, &y]
+            .iter()
+            .flat_map(|column| column.iter().map(|&v| Some(v)))
+            .collect();
+        let tensor = CausalTensor::new(flat, vec![n, 6])?;
+
+        let collinear = |selected: &[(usize, f64)]| selected.iter().filter(|(i, _)| *i <= 2).count();
+
+        // Averaging dilutes the duplicate's redundancy once an unrelated feature is selected
+        let pairwise = select_features(&tensor, 5, &MrmrOptions::new(3), &[])?;
+        assert_eq!(collinear(&pairwise), 2);
+
+        let options = MrmrOptions::new(3).with_redundancy(RedundancyMode::PartialCorrelation);
+        let partial = select_features(&tensor, 5, &options, &[])?;
+        assert_eq!(collinear(&partial), 1);
+        assert!(partial.iter().any(|(i, _)| *i == 4));
+        Ok(())
+    }
+
+    #[test]
+    fn test_min_score_stops_at_relevant_features() -> Result<()> {
+        let n = 500;
+        let mut columns: Vec<Vec<f64>> = (1..=10).map(|seed| noise(seed, n)).collect();
+        let u = noise(99, n);
+
+        // Only the first three columns drive the target; the other seven are pure noise
+        let y: Vec<f64> = (0..n).map(|i| columns[0][i] + columns[1][i] + columns[2][i] + 0.3 * u[i]).collect();
+        columns.push(y);
+
+        let flat: Vec<Option<f64>> = columns.iter()
+            .flat_map(|column| column.iter().map(|&v| Some(v)))
+            .collect();
+        let tensor = CausalTensor::new(flat, vec![n, 11])?;
+
+        let padded = select_features(&tensor, 10, &MrmrOptions::new(10), &[])?;
+        assert_eq!(padded.len(), 10);
+
+        let cut = select_features(&tensor, 10, &MrmrOptions::new(10).with_min_score(100.0), &[])?;
+        let mut indices: Vec<usize> = cut.iter().map(|(i, _)| *i).collect();
+        indices.sort_unstable();
+        assert_eq!(indices, vec![0, 1, 2]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_progress_reports_every_round() -> Result<()> {
+        let n = 300;
+        let mut columns: Vec<Vec<f64>> = (1..=5).map(|seed| noise(seed, n)).collect();
+        let u = noise(99, n);
+        let y: Vec<f64> = (0..n).map(|i| columns[0][i] + columns[1][i] + 0.3 * u[i]).collect();
+        columns.push(y);
+        let flat: Vec<Option<f64>> = columns.iter()
+            .flat_map(|column| column.iter().map(|&v| Some(v)))
+            .collect();
+        let tensor = CausalTensor::new(flat, vec![n, 6])?;
+
+        // The min_score cut ends the run after two rounds, which still reports 100%
+        let mut calls = Vec::new();
+        let options = MrmrOptions::new(4).with_min_score(100.0);
+        select_with_trace(&tensor, 5, &options, &[], &mut |selected, percent| {
+            calls.push((selected, percent));
+            Ok(())
+        })?;
+        assert_eq!(calls, vec![(1, 25.0), (2, 50.0), (2, 100.0)]);
+
+        // An error from the hook aborts the selection
+        let mut rounds = 0;
+        let aborted = select_with_trace(&tensor, 5, &MrmrOptions::new(4), &[], &mut |_, _| {
+            rounds += 1;
+            Err(DeepCausalityError::Mrmr("cancelled".into()))
+        });
+        assert!(aborted.is_err());
+        assert_eq!(rounds, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_trace_records_every_candidate_per_round() -> Result<()> {
+        let n = 200;
+        let (a, d, u1, u2) = (noise(1, n), noise(2, n), noise(3, n), noise(4, n));
+        let copy: Vec<f64> = (0..n).map(|i| a[i] + 0.3 * u1[i]).collect();
+        let y: Vec<f64> = (0..n).map(|i| a[i] + 0.6 * d[i] + 0.3 * u2[i]).collect();
+
+        let flat: Vec<Option<f64>> = [&a, &copy, &d, &y]
+            .iter()
+            .flat_map(|column| column.iter().map(|&v| Some(v)))
+            .collect();
+        let tensor = CausalTensor::new(flat, vec![n, 4])?;
+
+        let options = MrmrOptions::new(2);
+        let steps = select_with_trace(&tensor, 3, &options, &[], &mut |_, _| Ok(()))?;
+        let selected = select_features(&tensor, 3, &options, &[])?;
+        assert_eq!(steps.iter().map(|s| s.chosen).collect::<Vec<_>>(), vec![0, 2]);
+
+        // The pool shrinks by one per round; the first round scores on relevance alone
+        assert_eq!(steps[0].candidates.len(), 3);
+        assert_eq!(steps[1].candidates.len(), 2);
+        assert!(steps[0].candidates.iter().all(|c| c.redundancy == 0.0 && c.score == c.relevance));
+
+        // The chosen candidate's score is the one select_features reports
+        for (step, (index, score)) in steps.iter().zip(&selected) {
+            let chosen = step.candidates.iter().find(|c| c.index == *index).unwrap();
+            assert_eq!(chosen.score, *score);
+        }
+        let copy_score = steps[1].candidates.iter().find(|c| c.index == 1).unwrap();
+        assert!(copy_score.redundancy > 0.9);
+
+        // Describing the finished selection recovers the chosen candidates' terms
+        let described = describe_selection(&tensor, 3, &selected, &options)?;
+        for (described, step) in described.iter().zip(&steps) {
+            let chosen = step.candidates.iter().find(|c| c.index == step.chosen).unwrap();
+            assert_eq!((described.index, described.score), (chosen.index, chosen.score));
+            assert!((described.relevance - chosen.relevance).abs() < 1e-9);
+            assert!((described.redundancy - chosen.redundancy).abs() < 1e-9);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_binary_target_ranking_ignores_class_coding() -> Result<()> {
+        let n = 300;
+        let (a, d, e, u) = (noise(1, n), noise(2, n), noise(3, n), noise(4, n));
+        let label: Vec<f64> = (0..n).map(|i| if a[i] + 0.5 * d[i] + 0.3 * u[i] > 0.0 { 0.0 } else { 1.0 }).collect();
+        // The same two classes with their codes swapped and shifted, as another label order would give
+        let relabelled: Vec<f64> = label.iter().map(|&c| if c == 0.0 { 7.0 } else { 3.0 }).collect();
+
+        let rank = |target: &[f64]| -> Result<Vec<(usize, f64)>> {
+            let flat: Vec<Option<f64>> = [&a, &d, &e, &target.to_vec()]
+                .iter()
+                .flat_map(|column| column.iter().map(|&v| Some(v)))
+                .collect();
+            Ok(select_features(&CausalTensor::new(flat, vec![n, 4])?, 3, &MrmrOptions::new(3), &[])?)
+        };
+        let (original, swapped) = (rank(&label)?, rank(&relabelled)?);
+        assert_eq!(original.iter().map(|s| s.0).collect::<Vec<_>>(), swapped.iter().map(|s| s.0).collect::<Vec<_>>());
+        for ((_, x), (_, y)) in original.iter().zip(&swapped) {
+            assert!((x - y).abs() < 1e-9 * x.abs().max(1.0));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_multi_target_keeps_targets_out_of_each_others_pool() -> Result<()> {
+        let n = 300;
+        let (a, d, e, u1, u2) = (noise(1, n), noise(2, n), noise(3, n), noise(4, n), noise(5, n));
+
+        // The second target largely follows the first, so alone it would pick it up
+        let y1: Vec<f64> = (0..n).map(|i| a[i] + 0.3 * u1[i]).collect();
+        let y2: Vec<f64> = (0..n).map(|i| y1[i] + 0.5 * d[i] + 0.3 * u2[i]).collect();
+
+        let flat: Vec<Option<f64>> = [&a, &d, &e, &y1, &y2]
+            .iter()
+            .flat_map(|column| column.iter().map(|&v| Some(v)))
+            .collect();
+        let tensor = CausalTensor::new(flat, vec![n, 5])?;
+
+        let options = MrmrOptions::new(2);
+        let alone = select_features(&tensor, 4, &options, &[])?;
+        assert_eq!(alone[0].0, 3);
+
+        let mut percents = Vec::new();
+        let both = select_multi_target_with_trace(&tensor, &[3, 4], &options, &[], &mut |_, percent| {
+            percents.push(percent);
+            Ok(())
+        })?;
+        let chosen = |steps: &[Step]| steps.iter().map(|s| s.chosen).collect::<Vec<_>>();
+        assert_eq!(both.len(), 2);
+        assert_eq!(chosen(&both[0])[0], 0);
+        assert!(!chosen(&both[0]).contains(&4));
+        assert!(!chosen(&both[1]).contains(&3));
+
+        // Progress spans both targets
+        assert_eq!(percents, vec![25.0, 50.0, 75.0, 100.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_residualizing_removes_shared_covariate() -> Result<()> {
+        // x and y are related only through the covariate z
+        let n = 400;
+        let (z, ex, ey) = (noise(1, n), noise(2, n), noise(3, n));
+        let x: Vec<f64> = (0..n).map(|i| z[i] + 0.3 * ex[i]).collect();
+        let y: Vec<f64> = (0..n).map(|i| 2.0 * z[i] + 0.3 * ey[i]).collect();
+        let flat: Vec<Option<f64>> = [x, y, z].iter()
+            .flat_map(|column| column.iter().map(|&v| Some(v)))
+            .collect();
+        let tensor = CausalTensor::new(flat, vec![n, 3])?;
+        let names: Vec<String> = ["x", "y", "z"].iter().map(|s| s.to_string()).collect();
+
+        let raw = tensor.as_slice();
+        assert!(pearson(&raw[..n], &raw[n..2 * n]).0 > 0.9);
+
+        let residuals = residualize(&tensor, &[2], &names)?;
+        let res = residuals.as_slice();
+        assert!(pearson(&res[..n], &res[n..2 * n]).0.abs() < 0.15);
+        assert_eq!(&res[2 * n..], &raw[2 * n..]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_chunked_moments_select_like_full_tensor() -> Result<()> {
+        let n = 400;
+        let mut columns: Vec<Vec<Option<f64>>> = (1..=6)
+            .map(|seed| noise(seed, n).into_iter().map(Some).collect())
+            .collect();
+        // Gaps make every pair use its own complete rows
+        columns[2][17] = None;
+        columns[4][230] = None;
+        let y: Vec<Option<f64>> = (0..n)
+            .map(|i| Some(columns[0][i]? + 0.5 * columns[2][i]? + 0.2 * noise(9, n)[i]))
+            .collect();
+        columns.push(y);
+        let rows = |range: std::ops::Range<usize>| -> Result<CausalTensor<Option<f64>>> {
+            let data = columns.iter().flat_map(|c| c[range.clone()].iter().copied()).collect();
+            Ok(CausalTensor::new(data, vec![range.len(), 7])?)
+        };
+
+        let mut moments = CorrelationMoments::new(7);
+        for start in (0..n).step_by(150) {
+            moments.update(&rows(start..(start + 150).min(n))?)?;
+        }
+
+        let options = MrmrOptions::new(4);
+        let full = select_multi_target_with_trace(&rows(0..n)?, &[6], &options, &[], &mut |_, _| Ok(()))?;
+        let chunked = select_multi_target_from_moments(&moments, &[6], &[], &options, &[], &mut |_, _| Ok(()))?;
+        let chosen = |steps: &[Step]| steps.iter().map(|s| s.chosen).collect::<Vec<_>>();
+        assert_eq!(chosen(&chunked[0]), chosen(&full[0]));
+        for (a, b) in chunked[0].iter().zip(&full[0]) {
+            for (x, y) in a.candidates.iter().zip(&b.candidates) {
+                assert!((x.score - y.score).abs() < 1e-6 * y.score.abs().max(1.0));
+            }
+        }
+
+        let excluded = select_multi_target_from_moments(&moments, &[6], &[0], &options, &[], &mut |_, _| Ok(()))?;
+        assert!(!chosen(&excluded[0]).contains(&0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_information_matrix_is_symmetric_and_tracks_correlation() -> Result<()> {
+        let n = 400;
+        let x = noise(1, n);
+        let e = noise(2, n);
+        let y: Vec<f64> = x.iter().zip(&e).map(|(x, e)| x + 0.5 * e).collect();
+        let flat: Vec<Option<f64>> = [&x, &e, &y, &x].iter()
+            .flat_map(|column| column.iter().map(|&v| Some(v)))
+            .collect();
+        let tensor = CausalTensor::new(flat, vec![n, 4])?;
+
+        let matrix = information_matrix(&tensor, Some(2))?;
+        assert!(matrix[0][0].is_nan());
+        assert_eq!(matrix[0][2], matrix[2][0]);
+        assert!(matrix[0][2] > 0.5);
+        assert!(matrix[0][1] < 0.05);
+        // Identical columns hit the cap instead of infinity
+        assert!(matrix[0][3].is_finite() && matrix[0][3] > matrix[0][2]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_uniform_weights_match_unweighted_and_weights_shift_correlation() -> Result<()> {
+        let n = 200;
+        let x = noise(3, n);
+        let e = noise(4, n);
+        let y: Vec<f64> = x.iter().zip(&e).map(|(x, e)| x + e).collect();
+        let column = |v: &[f64]| v.iter().map(|&v| Some(v)).collect::<Vec<_>>();
+        let (x, y) = (column(&x), column(&y));
+
+        let (r, count) = pearson(&x, &y);
+        let (r_uniform, n_uniform) = weighted_pearson(&x, &y, &vec![2.5; n]);
+        assert!((r - r_uniform).abs() < 1e-12);
+        assert_eq!(count, n_uniform);
+
+        // Up-weighting rows where the noise is small strengthens the apparent link
+        let weights: Vec<f64> = e.iter().map(|e| if e.abs() < 0.2 { 10.0 } else { 1.0 }).collect();
+        let (r_weighted, n_eff) = weighted_pearson(&x, &y, &weights);
+        assert!(r_weighted > r);
+        assert!(n_eff < n);
+        Ok(())
+    }
+
+    #[test]
+    fn test_threaded_selection_matches_sequential() -> Result<()> {
+        let n = 300;
+        let mut columns: Vec<Vec<f64>> = (1..=8).map(|seed| noise(seed, n)).collect();
+        let u = noise(99, n);
+        let y: Vec<f64> = (0..n).map(|i| columns[0][i] + 0.7 * columns[3][i] + 0.4 * columns[5][i] + 0.3 * u[i]).collect();
+        columns.push(y);
+        let flat: Vec<Option<f64>> = columns.iter()
+            .flat_map(|column| column.iter().map(|&v| Some(v)))
+            .collect();
+        let tensor = CausalTensor::new(flat, vec![n, 9])?;
+
+        let sequential = select_features(&tensor, 8, &MrmrOptions::new(5), &[])?;
+        let threaded = select_features(&tensor, 8, &MrmrOptions::new(5).with_threads(4), &[])?;
+        assert_eq!(sequential, threaded);
+        Ok(())
+    }
+
+    #[test]
+    fn test_difference_scheme_tolerates_redundant_strong_feature() -> Result<()> {
+        let n = 200;
+        let (a, d, u1, u2) = (noise(1, n), noise(2, n), noise(3, n), noise(4, n));
+
+        // copy is a noisy duplicate of the dominant driver a; d is a weaker independent driver
+        let copy: Vec<f64> = (0..n).map(|i| a[i] + 0.3 * u1[i]).collect();
+        let y: Vec<f64> = (0..n).map(|i| a[i] + 0.6 * d[i] + 0.3 * u2[i]).collect();
+
+        let flat: Vec<Option<f64>> = [&a, &copy, &d, &y]
+            .iter()
+            .flat_map(|column| column.iter().map(|&v| Some(v)))
+            .collect();
+        let tensor = CausalTensor::new(flat, vec![n, 4])?;
+
+        // Dividing by redundancy favours the weak but novel driver...
+        let quotient = select_features(&tensor, 3, &MrmrOptions::new(2), &[])?;
+        assert_eq!(quotient[0].0, 0);
+        assert_eq!(quotient[1].0, 2);
+
+        // ...while subtracting a correlation barely dents the duplicate's F-statistic
+        let options = MrmrOptions::new(2).with_scheme(MrmrScheme::Difference);
+        let difference = select_features(&tensor, 3, &options, &[])?;
+        assert_eq!(difference[0].0, 0);
+        assert_eq!(difference[1].0, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_rejections_explain_redundant_feature() -> Result<()> {
+        let n = 200;
+        let (a, d, u1, u2) = (noise(1, n), noise(2, n), noise(3, n), noise(4, n));
+        let copy: Vec<f64> = (0..n).map(|i| a[i] + 0.3 * u1[i]).collect();
+        let y: Vec<f64> = (0..n).map(|i| a[i] + 0.6 * d[i] + 0.3 * u2[i]).collect();
+
+        let flat: Vec<Option<f64>> = [&a, &copy, &d, &y]
+            .iter()
+            .flat_map(|column| column.iter().map(|&v| Some(v)))
+            .collect();
+        let tensor = CausalTensor::new(flat, vec![n, 4])?;
+
+        let (selected, rejected) = select_with_rejections(&tensor, 3, &MrmrOptions::new(2), &[])?;
+        assert_eq!(selected.iter().map(|(i, _)| *i).collect::<Vec<_>>(), vec![0, 2]);
+
+        // The duplicate out-ranks the chosen weak driver on relevance alone, but a blocks it
+        assert_eq!(rejected.len(), 1);
+        let copy_rejection = &rejected[0];
+        assert_eq!(copy_rejection.index, 1);
+        let present = |v: &[f64]| v.iter().map(|&x| Some(x)).collect::<Vec<_>>();
+        let (r, m) = pearson(&present(&d), &present(&y));
+        assert!(copy_rejection.relevance > f_statistic(r, m));
+        assert!(copy_rejection.max_redundancy > 0.9);
+        assert_eq!(copy_rejection.blocked_by, Some(0));
+        assert_eq!(copy_rejection.round, 2);
+        assert!(!copy_rejection.grouped_out);
+        Ok(())
+    }
+
+    #[test]
+    fn test_feature_group_selects_single_representative() -> Result<()> {
+        let n = 100;
+        let a = noise(1, n);
+        let d = noise(2, n);
+        let u = noise(3, n);
+
+        // Two temperature probes reading the same signal, plus an independent vital
+        let probe_1: Vec<f64> = (0..n).map(|i| a[i] + 0.05 * u[i]).collect();
+        let probe_2: Vec<f64> = (0..n).map(|i| a[i] - 0.05 * u[i]).collect();
+        let y: Vec<f64> = (0..n).map(|i| a[i] + d[i]).collect();
+
+        let flat: Vec<Option<f64>> = [&probe_1, &probe_2, &d, &y]
+            .iter()
+            .flat_map(|column| column.iter().map(|&v| Some(v)))
+            .collect();
+        let tensor = CausalTensor::new(flat, vec![n, 4])?;
+
+        let ungrouped = select_features(&tensor, 3, &MrmrOptions::new(3), &[])?;
+        assert_eq!(ungrouped.len(), 3);
+
+        let grouped = select_features(&tensor, 3, &MrmrOptions::new(3), &[vec![0, 1]])?;
+        assert_eq!(grouped.len(), 2);
+        assert_eq!(grouped.iter().filter(|(i, _)| *i <= 1).count(), 1);
+        assert!(grouped.iter().any(|(i, _)| *i == 2));
+        Ok(())
+    }
+
+    /// Peak resident set size in kB, from /proc on Linux
+    fn peak_rss_kb() -> Option<u64> {
+        std::fs::read_to_string("/proc/self/status").ok()?
+            .lines()
+            .find_map(|line| line.strip_prefix("VmHWM:"))?
+            .trim()
+            .trim_end_matches("kB")
+            .trim()
+            .parse()
+            .ok()
+    }
+
+    /// The pre-cache selector: a dense n_cols x n_cols correlation matrix, then greedy
+    /// Pairwise/Quotient selection over it. Kept as the baseline for the lazy cache.
+    fn dense_select(tensor: &CausalTensor<Option<f64>>, target_idx: usize, max_features: usize) -> Vec<(usize, f64)> {
+        let height = tensor.shape()[0];
+        let columns: Vec<&[Option<f64>]> = tensor.as_slice().chunks(height).collect();
+        let n_cols = columns.len();
+        let relevance: Vec<f64> = columns.iter()
+            .map(|column| {
+                let (r, n) = pearson(column, columns[target_idx]);
+                f_statistic(r, n)
+            })
+            .collect();
+        let mut corr = vec![vec![0.0; n_cols]; n_cols];
+        for i in 0..n_cols {
+            corr[i][i] = 1.0;
+            for j in (i + 1)..n_cols {
+                let (r, _) = pearson(columns[i], columns[j]);
+                corr[i][j] = r;
+                corr[j][i] = r;
+            }
+        }
+
+        let mut remaining: Vec<usize> = (0..n_cols).filter(|&i| i != target_idx).collect();
+        let mut selected: Vec<(usize, f64)> = Vec::new();
+        while selected.len() < max_features && !remaining.is_empty() {
+            let (pos, score) = remaining.iter()
+                .enumerate()
+                .map(|(pos, &candidate)| {
+                    let score = if selected.is_empty() {
+                        relevance[candidate]
+                    } else {
+                        let redundancy = selected.iter().map(|&(s, _)| corr[candidate][s].abs()).sum::<f64>()
+                            / selected.len() as f64;
+                        relevance[candidate] / redundancy.max(MIN_REDUNDANCY)
+                    };
+                    (pos, score)
+                })
+                .max_by(|a, b| a.1.total_cmp(&b.1).then(b.0.cmp(&a.0)))
+                .expect("remaining candidates are non-empty");
+            selected.push((remaining.remove(pos), score));
+        }
+        selected
+    }
+
+    /// `n_features` noise columns and a target driven by the first ten, target last
+    fn wide_frame(n_rows: usize, n_features: usize) -> Result<CausalTensor<Option<f64>>> {
+        let mut flat: Vec<Option<f64>> = Vec::with_capacity(n_rows * (n_features + 1));
+        for seed in 0..n_features as u64 {
+            flat.extend(noise(seed + 1, n_rows).into_iter().map(Some));
+        }
+        let target: Vec<f64> = (0..n_rows)
+            .map(|i| (0..10.min(n_features)).map(|c| flat[c * n_rows + i].unwrap_or(0.0)).sum())
+            .collect();
+        flat.extend(target.into_iter().map(Some));
+        Ok(CausalTensor::new(flat, vec![n_rows, n_features + 1])?)
+    }
+
+    /// Run `select`, printing its wall time and peak RSS growth
+    fn measure(label: &str, select: impl FnOnce() -> Vec<(usize, f64)>) -> Vec<(usize, f64)> {
+        let rss_before = peak_rss_kb();
+        let start = std::time::Instant::now();
+        let selected = select();
+        let elapsed = start.elapsed();
+        let rss_growth = rss_before.zip(peak_rss_kb()).map(|(before, after)| after.saturating_sub(before));
+        println!(
+            "{}, 1000 x 2000 frame, 20 features: {:.2?}, peak RSS +{} kB",
+            label, elapsed, rss_growth.map_or("?".to_string(), |kb| kb.to_string()),
+        );
+        selected
+    }
+
+    #[test]
+    fn test_lazy_selection_matches_dense_baseline() -> Result<()> {
+        let tensor = wide_frame(300, 40)?;
+        let lazy = select_features(&tensor, 40, &MrmrOptions::new(8), &[])?;
+        let dense = dense_select(&tensor, 40, 8);
+        assert_eq!(lazy.iter().map(|(i, _)| *i).collect::<Vec<_>>(), dense.iter().map(|(i, _)| *i).collect::<Vec<_>>());
+        for ((_, a), (_, b)) in lazy.iter().zip(&dense) {
+            assert!((a - b).abs() <= 1e-9 * b.abs().max(1.0));
+        }
+        Ok(())
+    }
+
+    // Wide-frame benchmarks for the native selector's lazy redundancy cache against the
+    // dense matrix it replaced. Both measure the native selector only; `run_mrmr` with
+    // default options uses the upstream selector and is not covered. Run each in its own
+    // process so peak RSS is not shared, e.g.
+    // `cargo test --release -p icu_sepsis_backend -- --ignored --nocapture bench_wide_frame_lazy`.
+
+    #[test]
+    #[ignore]
+    fn bench_wide_frame_lazy_native_selection() -> Result<()> {
+        let tensor = wide_frame(1000, 2000)?;
+        let selected = measure("lazy native selector", || {
+            select_features(&tensor, 2000, &MrmrOptions::new(20), &[]).expect("selection succeeds")
+        });
+        assert_eq!(selected.len(), 20);
+        Ok(())
+    }
+
+    #[test]
+    #[ignore]
+    fn bench_wide_frame_dense_baseline() -> Result<()> {
+        let tensor = wide_frame(1000, 2000)?;
+        let selected = measure("dense baseline", || dense_select(&tensor, 2000, 20));
+        assert_eq!(selected.len(), 20);
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 use serde::Deserialize;
 use std::fs;
-use anyhow::{Context, Result};
+use crate::error::{DeepCausalityError, Result};
+use crate::causality::{DiscretizationStrategy, Discretizer, SurdScoreWeights};
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct Config {
@@ -31,14 +32,50 @@ pub struct ExperimentConfig {
 pub struct CausalityConfig {
     pub significance_threshold: f64,
     pub max_features: usize,
+    /// Drop mRMR features scoring below this cutoff (omit to always return max_features)
+    #[serde(default)]
+    pub min_score: Option<f64>,
+    /// Also report mRMR scores rescaled so the top feature is 1.0
+    #[serde(default)]
+    pub normalize_scores: bool,
+    /// Number of bins used when discretizing continuous columns
+    #[serde(default = "default_n_bins")]
+    pub n_bins: usize,
+    /// Binning strategy applied to the mRMR and SURD inputs (omit to pass raw values through)
+    #[serde(default)]
+    pub discretization: Option<DiscretizationStrategy>,
+    /// Minimum samples per joint SURD cell before estimates are flagged as unreliable
+    #[serde(default = "default_min_samples_per_bin")]
+    pub min_samples_per_bin: usize,
+    /// Weights of the SURD terms in the sepsis specificity score (unique-only by default)
+    #[serde(default)]
+    pub surd_score_weights: SurdScoreWeights,
+    /// Skip features SURD fails on (e.g. all-null columns) instead of aborting the decomposition
+    #[serde(default)]
+    pub surd_skip_failures: bool,
+}
+
+fn default_n_bins() -> usize {
+    10
+}
+
+fn default_min_samples_per_bin() -> usize {
+    5
+}
+
+impl CausalityConfig {
+    /// The binning both mRMR and SURD use, if discretization is enabled
+    pub fn discretizer(&self) -> Option<Discretizer> {
+        self.discretization.map(|strategy| Discretizer::new(self.n_bins, strategy))
+    }
 }
 
 impl Config {
     pub fn load(path: &str) -> Result<Self> {
         let content = fs::read_to_string(path)
-            .with_context(|| format!("Failed to read config file at {}", path))?;
+            .map_err(|e| DeepCausalityError::Config(format!("Failed to read config file at {}: {}", path, e)))?;
         let config: Config = toml::from_str(&content)
-            .context("Failed to parse config file")?;
+            .map_err(|e| DeepCausalityError::Config(format!("Failed to parse config file {}: {}", path, e)))?;
         Ok(config)
     }
 }
@@ -1,6 +1,6 @@
 use deep_causality::CausaloidGraph;
 use polars::prelude::*;
-use anyhow::Result;
+use crate::error::Result;
 
 pub struct PatientContext {
     pub id: String,
@@ -1,6 +1,11 @@
+pub mod profile;
+pub mod source;
+pub mod synthetic;
+
 use polars::prelude::*;
-use anyhow::{Result, Context};
+use crate::error::{DeepCausalityError, Result};
 use tracing::info;
+use self::synthetic::{SyntheticConfig, SyntheticDataset};
 
 pub struct DataLoader;
 
@@ -10,50 +15,258 @@ impl DataLoader {
         info!("Loading parquet file: {}", path);
         
         let file = std::fs::File::open(path)
-            .with_context(|| format!("Failed to open file: {}", path))?;
+            .map_err(|e| DeepCausalityError::DataLoad(format!("Failed to open file {}: {}", path, e)))?;
         
         let df = ParquetReader::new(file)
             .finish()
-            .with_context(|| format!("Failed to parse parquet: {}", path))?;
+            .map_err(|e| DeepCausalityError::DataLoad(format!("Failed to parse parquet {}: {}", path, e)))?;
         
         info!("Loaded {} rows x {} columns", df.height(), df.width());
         Ok(df)
     }
 
+    /// Load every parquet file matching a glob pattern (e.g. `data/icu/day_*.parquet`)
+    /// as one DataFrame. A plain path matches just that file.
+    pub fn load_parquet_glob(pattern: &str) -> Result<DataFrame> {
+        Self::load_parquet_files(&Self::expand_glob(pattern)?)
+    }
+
+    /// Files matching a glob pattern, sorted; an error when nothing matches
+    pub fn expand_glob(pattern: &str) -> Result<Vec<String>> {
+        let mut paths: Vec<String> = glob::glob(pattern)
+            .map_err(|e| DeepCausalityError::DataLoad(format!("Invalid glob pattern {}: {}", pattern, e)))?
+            .map(|entry| entry.map(|path| path.to_string_lossy().into_owned()))
+            .collect::<std::result::Result<_, _>>()
+            .map_err(|e| DeepCausalityError::DataLoad(format!("Failed to expand glob {}: {}", pattern, e)))?;
+        paths.sort();
+        if paths.is_empty() {
+            return Err(DeepCausalityError::DataLoad(format!("No parquet files match {}", pattern)));
+        }
+        Ok(paths)
+    }
+
+    /// Load parquet files with identical schemas (e.g. from `expand_glob`) as one DataFrame
+    pub fn load_parquet_files(paths: &[String]) -> Result<DataFrame> {
+        let Some(first) = paths.first() else {
+            return Err(DeepCausalityError::DataLoad("No parquet files given".into()));
+        };
+        let mut combined = Self::load_parquet(first)?;
+        let schema = combined.schema();
+        for path in &paths[1..] {
+            let df = Self::load_parquet(path)?;
+            let problems = Self::schema_differences(&schema, &df.schema());
+            if !problems.is_empty() {
+                return Err(DeepCausalityError::DataLoad(format!(
+                    "Schema of {} differs from {}: {}", path, first, problems.join("; ")
+                )));
+            }
+            combined.vstack_mut(&df)
+                .map_err(|e| DeepCausalityError::DataLoad(format!("Failed to append {}: {}", path, e)))?;
+        }
+        combined.as_single_chunk_par();
+
+        if paths.len() > 1 {
+            info!("Combined {} parquet files into {} rows", paths.len(), combined.height());
+        }
+        Ok(combined)
+    }
+
+    /// Human-readable differences between two schemas (empty when identical)
+    fn schema_differences(expected: &Schema, found: &Schema) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (name, dtype) in expected.iter() {
+            match found.get(name) {
+                None => problems.push(format!("missing column {}", name)),
+                Some(other) if other != dtype => {
+                    problems.push(format!("column {} is {} instead of {}", name, other, dtype))
+                }
+                _ => {}
+            }
+        }
+        for (name, _) in found.iter() {
+            if expected.get(name).is_none() {
+                problems.push(format!("unexpected column {}", name));
+            }
+        }
+        if problems.is_empty() && expected != found {
+            problems.push("column order differs".to_string());
+        }
+        problems
+    }
+
     /// Load a CSV file into a Polars DataFrame
     pub fn load_csv(path: &str) -> Result<DataFrame> {
         info!("Loading CSV file: {}", path);
         
-        let df = CsvReader::from_path(path)?
-            .has_header(true)
-            .finish()
-            .with_context(|| format!("Failed to parse CSV: {}", path))?;
+        let df = CsvReader::from_path(path)
+            .and_then(|reader| reader.has_header(true).finish())
+            .map_err(|e| DeepCausalityError::DataLoad(format!("Failed to parse CSV {}: {}", path, e)))?;
         
         info!("Loaded {} rows x {} columns", df.height(), df.width());
         Ok(df)
     }
 
+    /// Load an Arrow IPC (Feather v2) file into a Polars DataFrame
+    pub fn load_ipc(path: &str) -> Result<DataFrame> {
+        info!("Loading IPC file: {}", path);
+
+        let file = std::fs::File::open(path)
+            .map_err(|e| DeepCausalityError::DataLoad(format!("Failed to open file {}: {}", path, e)))?;
+
+        let df = IpcReader::new(file)
+            .finish()
+            .map_err(|e| DeepCausalityError::DataLoad(format!("Failed to parse IPC {}: {}", path, e)))?;
+
+        info!("Loaded {} rows x {} columns", df.height(), df.width());
+        Ok(df)
+    }
+
+    /// Load a Parquet, Arrow IPC/Feather or CSV file, picking the reader by extension
+    pub fn load_file(path: &str) -> Result<DataFrame> {
+        let extension = std::path::Path::new(path)
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        match extension.as_str() {
+            "parquet" | "pq" => Self::load_parquet(path),
+            "feather" | "arrow" | "ipc" => Self::load_ipc(path),
+            "csv" => Self::load_csv(path),
+            _ => Err(DeepCausalityError::DataLoad(format!(
+                "Unsupported file type for {} (expected .parquet, .feather, .arrow, .ipc or .csv)", path
+            ))),
+        }
+    }
+
+    /// Check that `required` and `numeric` columns exist and that `numeric` ones cast
+    /// losslessly to Float64, reporting every problem in one `Schema` error
+    pub fn validate_schema(df: &DataFrame, required: &[&str], numeric: &[&str]) -> Result<()> {
+        let names = df.get_column_names();
+        let mut missing: Vec<String> = Vec::new();
+        let mut non_numeric: Vec<String> = Vec::new();
+
+        for &column in required.iter().chain(numeric) {
+            if !names.contains(&column) && !missing.iter().any(|m| m == column) {
+                missing.push(column.to_string());
+            }
+        }
+        for &column in numeric {
+            if let Ok(series) = df.column(column) {
+                if series.strict_cast(&DataType::Float64).is_err() {
+                    non_numeric.push(column.to_string());
+                }
+            }
+        }
+
+        if missing.is_empty() && non_numeric.is_empty() {
+            return Ok(());
+        }
+        Err(DeepCausalityError::Schema { missing, non_numeric })
+    }
+
     /// Filter DataFrame by a boolean column value
     pub fn filter_by_label(df: &DataFrame, column: &str, value: bool) -> Result<DataFrame> {
         let mask = df.column(column)?
             .bool()?
             .equal(value);
         
-        df.filter(&mask)
-            .context("Failed to filter DataFrame")
+        Ok(df.filter(&mask)?)
     }
 
     /// Get summary statistics for a DataFrame
     pub fn describe(df: &DataFrame) -> Result<DataFrame> {
-        df.describe(None)
-            .context("Failed to generate summary statistics")
+        Ok(df.describe(None)?)
+    }
+
+    /// Percentiles reported by `describe_extended`, as (column name, quantile)
+    const PERCENTILES: [(&'static str, f64); 5] =
+        [("p05", 0.05), ("p25", 0.25), ("p50", 0.5), ("p75", 0.75), ("p95", 0.95)];
+
+    /// One row per numeric column: count, nulls, mean, std, 5th/25th/50th/75th/95th
+    /// percentiles and skewness, all computed over non-null values
+    pub fn describe_extended(df: &DataFrame) -> Result<DataFrame> {
+        let mut names: Vec<String> = Vec::new();
+        let mut counts: Vec<u32> = Vec::new();
+        let mut nulls: Vec<u32> = Vec::new();
+        let mut means: Vec<Option<f64>> = Vec::new();
+        let mut stds: Vec<Option<f64>> = Vec::new();
+        let mut skews: Vec<Option<f64>> = Vec::new();
+        let mut percentiles: Vec<Vec<Option<f64>>> = vec![Vec::new(); Self::PERCENTILES.len()];
+
+        for series in df.get_columns() {
+            if !series.dtype().is_numeric() {
+                continue;
+            }
+            let mut values: Vec<f64> = series.cast(&DataType::Float64)?
+                .f64()?
+                .into_iter()
+                .flatten()
+                .filter(|v| v.is_finite())
+                .collect();
+            values.sort_by(|a, b| a.total_cmp(b));
+
+            let (mean, std, skew) = moments(&values);
+            names.push(series.name().to_string());
+            counts.push(values.len() as u32);
+            nulls.push(series.null_count() as u32);
+            means.push(mean);
+            stds.push(std);
+            skews.push(skew);
+            for (column, &(_, q)) in percentiles.iter_mut().zip(&Self::PERCENTILES) {
+                column.push(percentile(&values, q));
+            }
+        }
+
+        let mut columns = vec![
+            Series::new("column", names),
+            Series::new("count", counts),
+            Series::new("null_count", nulls),
+            Series::new("mean", means),
+            Series::new("std", stds),
+        ];
+        columns.extend(Self::PERCENTILES.iter().zip(percentiles).map(|(&(name, _), values)| Series::new(name, values)));
+        columns.push(Series::new("skewness", skews));
+
+        Ok(DataFrame::new(columns)?)
+    }
+
+    /// Generate a synthetic cohort with known sepsis drivers (see `synthetic::SyntheticConfig`)
+    pub fn synthetic(config: &SyntheticConfig) -> Result<SyntheticDataset> {
+        let dataset = synthetic::generate(config)?;
+        info!("Generated synthetic cohort: {} rows, drivers {:?}", dataset.df.height(), dataset.true_drivers);
+        Ok(dataset)
     }
 
     /// Sample n rows from DataFrame (for testing with large datasets)
     pub fn sample(df: &DataFrame, n: usize, seed: Option<u64>) -> Result<DataFrame> {
-        df.sample_n_literal(n, false, false, seed)
-            .context("Failed to sample DataFrame")
+        Ok(df.sample_n_literal(n, false, false, seed)?)
+    }
+}
+
+/// Linearly interpolated percentile of sorted values (`q` in 0..=1)
+fn percentile(sorted: &[f64], q: f64) -> Option<f64> {
+    let last = sorted.len().checked_sub(1)?;
+    let rank = q * last as f64;
+    let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+    Some(sorted[lo] + (sorted[hi] - sorted[lo]) * (rank - lo as f64))
+}
+
+/// Mean, sample standard deviation, and (biased) Fisher-Pearson skewness
+fn moments(values: &[f64]) -> (Option<f64>, Option<f64>, Option<f64>) {
+    let n = values.len() as f64;
+    if values.is_empty() {
+        return (None, None, None);
+    }
+    let mean = values.iter().sum::<f64>() / n;
+    if values.len() < 2 {
+        return (Some(mean), None, None);
     }
+
+    let m2 = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / n;
+    let m3 = values.iter().map(|v| (v - mean).powi(3)).sum::<f64>() / n;
+    let std = (m2 * n / (n - 1.0)).sqrt();
+    // A constant column has no defined skew
+    let skew = (m2 > 0.0).then(|| m3 / m2.powf(1.5));
+    (Some(mean), Some(std), skew)
 }
 
 #[cfg(test)]
@@ -65,4 +278,87 @@ mod tests {
         // Basic existence test
         let _loader = DataLoader;
     }
+
+    #[test]
+    fn test_describe_extended() -> Result<()> {
+        // 1..=101 with a null: median 51, 5th percentile 6, symmetric
+        let mut uniform: Vec<Option<f64>> = (1..=101).map(|v| Some(v as f64)).collect();
+        uniform.push(None);
+        let skewed: Vec<Option<f64>> = (0..102).map(|v| Some((v as f64 / 10.0).exp())).collect();
+        let labels: Vec<&str> = vec!["a"; 102];
+        let df = df!["HR" => uniform, "Lactate" => skewed, "Unit" => labels]?;
+
+        let summary = DataLoader::describe_extended(&df)?;
+        assert_eq!(summary.height(), 2);
+
+        let stat = |name: &str, row: usize| summary.column(name).unwrap().f64().unwrap().get(row).unwrap();
+        assert_eq!(summary.column("null_count")?.u32()?.get(0), Some(1));
+        assert!((stat("p50", 0) - 51.0).abs() < 1e-12);
+        assert!((stat("p05", 0) - 6.0).abs() < 1e-12);
+        assert!(stat("skewness", 0).abs() < 1e-12);
+        assert!(stat("skewness", 1) > 1.0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_validate_schema_reports_all_problems() -> Result<()> {
+        let df = df![
+            "HR" => [80.0, 90.0],
+            "Unit" => ["MICU", "SICU"],
+            "SepsisLabel" => [0, 1]
+        ]?;
+        DataLoader::validate_schema(&df, &["Unit"], &["HR", "SepsisLabel"])?;
+
+        let err = DataLoader::validate_schema(&df, &["Patient_ID", "SepsisLabel"], &["HR", "Unit"]).unwrap_err();
+        match err {
+            DeepCausalityError::Schema { missing, non_numeric } => {
+                assert_eq!(missing, vec!["Patient_ID"]);
+                assert_eq!(non_numeric, vec!["Unit"]);
+            }
+            other => panic!("expected a schema error, got {}", other),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_parquet_glob() -> Result<()> {
+        // Per-process, so concurrent test runs don't share files
+        let dir = std::env::temp_dir().join(format!("load_parquet_glob_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        let write = |name: &str, mut df: DataFrame| -> Result<()> {
+            let file = std::fs::File::create(dir.join(name))?;
+            ParquetWriter::new(file).finish(&mut df)?;
+            Ok(())
+        };
+
+        write("part_1.parquet", df!["HR" => [80.0, 90.0], "SepsisLabel" => [0, 1]]?)?;
+        write("part_2.parquet", df!["HR" => [70.0], "SepsisLabel" => [0]]?)?;
+        let pattern = dir.join("part_*.parquet");
+        let df = DataLoader::load_parquet_glob(pattern.to_str().unwrap())?;
+        assert_eq!(df.shape(), (3, 2));
+
+        // A file with a diverging schema is rejected with a clear message
+        write("part_3.parquet", df!["HR" => [70.0], "Lactate" => [1.2]]?)?;
+        let err = DataLoader::load_parquet_glob(pattern.to_str().unwrap()).unwrap_err();
+        assert!(matches!(err, DeepCausalityError::DataLoad(_)));
+        assert!(err.to_string().contains("missing column SepsisLabel"));
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_load_file_dispatches_on_extension() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("load_file_test_{}.feather", std::process::id()));
+        let mut df = df!["HR" => [80.0, 90.0], "SepsisLabel" => [0, 1]]?;
+        IpcWriter::new(std::fs::File::create(&path)?).finish(&mut df)?;
+
+        let loaded = DataLoader::load_file(path.to_str().unwrap())?;
+        assert!(loaded.equals(&df));
+        std::fs::remove_file(&path)?;
+
+        let err = DataLoader::load_file("vitals.xlsx").unwrap_err();
+        assert!(matches!(err, DeepCausalityError::DataLoad(_)));
+        Ok(())
+    }
 }
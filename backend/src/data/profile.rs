@@ -0,0 +1,147 @@
+//! Per-column data-quality profile
+//!
+//! mRMR scores a column 0 when it is constant, and can't score it at all when it
+//! barely overlaps the target. Profiling the tensor first shows which columns will
+//! fall out and why, instead of leaving users to guess from a short ranking.
+
+use crate::error::{DeepCausalityError, Result};
+use deep_causality_tensor::CausalTensor;
+use serde::{Serialize, Deserialize};
+use std::collections::HashSet;
+
+/// Share of missing values above which a column is flagged
+const HIGH_MISSING_FRACTION: f64 = 0.5;
+
+/// Fewest rows shared with the target for which mRMR's F-statistic is non-zero
+const MIN_TARGET_OVERLAP: usize = 3;
+
+/// Quality summary of one column
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ColumnProfile {
+    pub name: String,
+    /// Present (non-missing) values
+    pub count: usize,
+    pub missing: usize,
+    pub missing_fraction: f64,
+    /// Distinct present values
+    pub n_unique: usize,
+    /// At most one distinct value, so no correlation with anything
+    pub constant: bool,
+    pub mean: Option<f64>,
+    /// Sample standard deviation
+    pub std: Option<f64>,
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+    /// Rows where both this column and the target are present, when a target was given
+    pub paired_with_target: Option<usize>,
+    /// Human-readable problems likely to make mRMR skip or zero-score the column
+    pub issues: Vec<String>,
+}
+
+/// Profile every column of a column-major tensor; with `target`, also count each
+/// column's overlap with it
+pub fn profile_tensor(
+    tensor: &CausalTensor<Option<f64>>,
+    col_names: &[String],
+    target: Option<&str>,
+) -> Result<Vec<ColumnProfile>> {
+    let [height, width] = *tensor.shape() else {
+        return Err(DeepCausalityError::TensorConversion("Expected a 2D tensor".into()));
+    };
+    if col_names.len() != width {
+        return Err(DeepCausalityError::TensorConversion(format!(
+            "Got {} column names for a tensor with {} columns", col_names.len(), width
+        )));
+    }
+    let target_idx = target
+        .map(|t| col_names.iter().position(|n| n == t).ok_or_else(|| DeepCausalityError::TargetNotFound(t.to_string())))
+        .transpose()?;
+
+    let columns: Vec<&[Option<f64>]> = tensor.as_slice().chunks(height.max(1)).collect();
+    Ok(col_names.iter()
+        .enumerate()
+        .map(|(idx, name)| {
+            let column = columns.get(idx).copied().unwrap_or(&[]);
+            let paired_with_target = target_idx.map(|t| {
+                column.iter().zip(columns[t]).filter(|(v, t)| v.is_some() && t.is_some()).count()
+            });
+            profile_column(name, column, height, paired_with_target, Some(idx) == target_idx)
+        })
+        .collect())
+}
+
+fn profile_column(
+    name: &str,
+    column: &[Option<f64>],
+    height: usize,
+    paired_with_target: Option<usize>,
+    is_target: bool,
+) -> ColumnProfile {
+    let values: Vec<f64> = column.iter().flatten().copied().collect();
+    let count = values.len();
+    let missing = height - count;
+    // -0.0 and 0.0 are the same value
+    let n_unique = values.iter().map(|v| (v + 0.0).to_bits()).collect::<HashSet<_>>().len();
+    let mean = (count > 0).then(|| values.iter().sum::<f64>() / count as f64);
+    let std = mean.filter(|_| count > 1)
+        .map(|mean| (values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (count - 1) as f64).sqrt());
+    let missing_fraction = if height > 0 { missing as f64 / height as f64 } else { 0.0 };
+
+    let mut issues = Vec::new();
+    if count == 0 {
+        issues.push("all values missing".to_string());
+    } else if n_unique == 1 {
+        issues.push(if is_target {
+            "target is constant, so every feature scores 0".to_string()
+        } else {
+            "constant, so it carries no signal".to_string()
+        });
+    }
+    if count > 0 && missing_fraction > HIGH_MISSING_FRACTION {
+        issues.push(format!("{:.0}% of values missing", missing_fraction * 100.0));
+    }
+    if let Some(paired) = paired_with_target.filter(|&p| !is_target && count > 0 && p < MIN_TARGET_OVERLAP) {
+        issues.push(format!("only {} rows overlap the target; mRMR needs at least {}", paired, MIN_TARGET_OVERLAP));
+    }
+
+    ColumnProfile {
+        name: name.to_string(),
+        count,
+        missing,
+        missing_fraction,
+        n_unique,
+        constant: n_unique <= 1,
+        mean,
+        std,
+        min: values.iter().copied().reduce(f64::min),
+        max: values.iter().copied().reduce(f64::max),
+        paired_with_target: paired_with_target.filter(|_| !is_target),
+        issues,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::tensor_adapter::TensorAdapter;
+
+    #[test]
+    fn test_profile_flags_columns_mrmr_cannot_use() -> Result<()> {
+        let hr = [Some(80.0), Some(95.0), Some(110.0), Some(95.0), None, Some(70.0)];
+        let flat = [Some(1.0); 6];
+        let sparse = [None, None, None, None, Some(2.0), Some(3.0)];
+        let target = [Some(0.0), Some(1.0), Some(1.0), Some(0.0), Some(1.0), None];
+        let data = [&hr, &flat, &sparse, &target].iter().flat_map(|c| c.iter().copied()).collect();
+        let tensor = TensorAdapter::column_major_tensor(data, 6, 4)?;
+        let names: Vec<String> = ["HR", "Flat", "Sparse", "y"].iter().map(|s| s.to_string()).collect();
+
+        let profile = profile_tensor(&tensor, &names, Some("y"))?;
+        assert_eq!((profile[0].count, profile[0].n_unique, profile[0].paired_with_target), (5, 4, Some(4)));
+        assert!(profile[0].issues.is_empty());
+        assert!(profile[1].constant && profile[1].issues[0].starts_with("constant"));
+        assert_eq!(profile[2].paired_with_target, Some(1));
+        assert_eq!(profile[2].issues.len(), 2);
+        assert_eq!(profile[3].paired_with_target, None);
+        Ok(())
+    }
+}
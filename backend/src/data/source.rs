@@ -0,0 +1,102 @@
+//! Pluggable inputs for causal discovery
+//!
+//! Anything that can produce a column-major tensor with column names can feed
+//! the pipeline, so database or in-memory data need not round-trip through parquet.
+
+use polars::prelude::DataFrame;
+use deep_causality_tensor::CausalTensor;
+use crate::error::{DeepCausalityError, Result};
+use crate::utils::tensor_adapter::TensorAdapter;
+
+/// A source of tabular data for discovery
+pub trait DataSource {
+    /// Build the column-major tensor and its column names
+    #[allow(clippy::wrong_self_convention)] // sources are reusable, so conversion borrows
+    fn into_tensor(&self) -> Result<(CausalTensor<Option<f64>>, Vec<String>)>;
+}
+
+impl DataSource for DataFrame {
+    fn into_tensor(&self) -> Result<(CausalTensor<Option<f64>>, Vec<String>)> {
+        TensorAdapter::df_to_tensor(self)
+    }
+}
+
+/// Row-major values with named columns; NaN marks a missing value
+#[derive(Debug, Clone)]
+pub struct RowSource {
+    pub column_names: Vec<String>,
+    pub rows: Vec<Vec<f64>>,
+}
+
+impl RowSource {
+    pub fn new(column_names: Vec<String>, rows: Vec<Vec<f64>>) -> Self {
+        Self { column_names, rows }
+    }
+}
+
+impl DataSource for RowSource {
+    fn into_tensor(&self) -> Result<(CausalTensor<Option<f64>>, Vec<String>)> {
+        let width = self.column_names.len();
+        let (height, n_cols) = TensorAdapter::check_rectangular(&self.rows)?;
+        if height > 0 && n_cols != width {
+            return Err(DeepCausalityError::TensorConversion(format!(
+                "Rows have {} values but there are {} column names", n_cols, width
+            )));
+        }
+
+        let flat_data: Vec<Option<f64>> = (0..width)
+            .flat_map(|j| self.rows.iter().map(move |row| Some(row[j]).filter(|v| !v.is_nan())))
+            .collect();
+        let tensor = TensorAdapter::column_major_tensor(flat_data, height, width)?;
+
+        Ok((tensor, self.column_names.clone()))
+    }
+}
+
+/// A source backed by a closure, e.g. a database query run on demand
+pub struct FnSource<F>(pub F);
+
+impl<F> DataSource for FnSource<F>
+where
+    F: Fn() -> Result<(CausalTensor<Option<f64>>, Vec<String>)>,
+{
+    fn into_tensor(&self) -> Result<(CausalTensor<Option<f64>>, Vec<String>)> {
+        (self.0)()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::causality::{CausalDiscovery, MrmrOptions};
+
+    #[test]
+    fn test_non_dataframe_sources() -> Result<()> {
+        let names: Vec<String> = ["HR", "Lactate", "SepsisLabel"].iter().map(|s| s.to_string()).collect();
+        let rows = vec![
+            vec![80.0, 1.0, 0.0],
+            vec![95.0, 2.5, 1.0],
+            vec![110.0, f64::NAN, 1.0],
+            vec![85.0, 1.2, 0.0],
+            vec![120.0, 4.5, 1.0],
+            vec![90.0, 1.8, 0.0],
+        ];
+        let source = RowSource::new(names.clone(), rows);
+
+        let (tensor, _) = source.into_tensor()?;
+        assert_eq!(tensor.shape(), [6, 3]);
+        assert_eq!(tensor.as_slice()[8], None);
+
+        let options = MrmrOptions::new(2);
+        let from_rows = CausalDiscovery::run_mrmr(&source, "SepsisLabel", &options)?;
+        assert!(!from_rows.is_empty());
+
+        let closure = FnSource(|| source.into_tensor());
+        let from_closure = CausalDiscovery::run_mrmr(&closure, "SepsisLabel", &options)?;
+        assert_eq!(from_rows, from_closure);
+
+        let ragged = RowSource::new(names, vec![vec![1.0, 2.0]]);
+        assert!(ragged.into_tensor().is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+//! Error types for the ICU sepsis backend
+//!
+//! Library modules return [`DeepCausalityError`] so callers can match on the
+//! failure kind; `anyhow` is only used at the binary boundary.
+
+use polars::prelude::PolarsError;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum DeepCausalityError {
+    /// Reading or combining input data failed
+    #[error("Failed to load data: {0}")]
+    DataLoad(String),
+
+    /// Columns an analysis relies on are absent or not numeric
+    #[error("Invalid data schema: missing columns {missing:?}, non-numeric columns {non_numeric:?}")]
+    Schema {
+        missing: Vec<String>,
+        non_numeric: Vec<String>,
+    },
+
+    /// A requested target column is not present in the data
+    #[error("Target column {0} not found")]
+    TargetNotFound(String),
+
+    /// Converting between DataFrames, tensors, and tensor caches failed
+    #[error("Tensor conversion failed: {0}")]
+    TensorConversion(String),
+
+    /// mRMR feature selection failed or was given invalid input
+    #[error("mRMR failed: {0}")]
+    Mrmr(String),
+
+    /// mRMR ran but every candidate was filtered out or scored below the cutoff
+    #[error(
+        "mRMR selected no features for target {0}. Likely causes: include/exclude filters removing \
+         every candidate, a min_score cutoff above every score, constant or all-null feature columns, \
+         or the wrong target column"
+    )]
+    NoFeaturesSelected(String),
+
+    /// SURD decomposition failed or was given invalid input
+    #[error("SURD failed: {0}")]
+    Surd(String),
+
+    /// Transfer-entropy estimation was given invalid input
+    #[error("Transfer entropy failed: {0}")]
+    TransferEntropy(String),
+
+    /// Configuration could not be read or has invalid values
+    #[error("Invalid configuration: {0}")]
+    Config(String),
+
+    /// ROC/PR evaluation was given invalid scores or labels
+    #[error("Evaluation failed: {0}")]
+    Evaluation(String),
+
+    /// Writing or rendering a graph export failed
+    #[error("Export failed: {0}")]
+    Export(String),
+
+    #[error(transparent)]
+    Polars(#[from] PolarsError),
+
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, DeepCausalityError>;
@@ -231,6 +231,95 @@ impl EthosRule for MaxUncertaintyThreshold {
     }
 }
 
+/// Rule: Block prediction if a high-importance feature is missing
+pub struct CriticalFeatureMissingRule {
+    /// (feature, importance weight such as its mRMR score, tolerance); a missing feature
+    /// blocks when its weighted missingness exceeds its own tolerance
+    features: Vec<(String, f64, f64)>,
+}
+
+impl CriticalFeatureMissingRule {
+    pub fn new(features: Vec<(&str, f64, f64)>) -> Self {
+        Self {
+            features: features
+                .into_iter()
+                .map(|(name, weight, tolerance)| (name.to_string(), weight, tolerance))
+                .collect(),
+        }
+    }
+
+    /// Build the rule from mRMR feature rankings, with the same tolerance for every feature
+    pub fn from_mrmr_results(features: &[(String, f64)], tolerance: f64) -> Self {
+        Self {
+            features: features.iter().map(|(name, weight)| (name.clone(), *weight, tolerance)).collect(),
+        }
+    }
+
+    /// Missing features whose weight exceeds their tolerance, most important first
+    fn missing_critical(&self, data: &PatientData) -> Vec<&(String, f64, f64)> {
+        let mut missing: Vec<&(String, f64, f64)> = self.features
+            .iter()
+            .filter(|(name, weight, tolerance)| {
+                // A missing feature counts fully, so its weighted missingness is its weight
+                *weight > *tolerance && data.is_vital_missing(name) && data.is_lab_missing(name)
+            })
+            .collect();
+        missing.sort_by(|a, b| b.1.total_cmp(&a.1));
+        missing
+    }
+}
+
+impl EthosRule for CriticalFeatureMissingRule {
+    fn id(&self) -> &str {
+        "ETHOS-003"
+    }
+
+    fn description(&self) -> &str {
+        "Block prediction if a high-importance feature is missing"
+    }
+
+    fn check(&self, data: &PatientData) -> bool {
+        self.missing_critical(data).is_empty()
+    }
+
+    fn explain(&self, data: &PatientData) -> CounterfactualExplanation {
+        let missing = self.missing_critical(data);
+        if missing.is_empty() {
+            return CounterfactualExplanation::new(
+                "Sepsis Risk Prediction",
+                "No high-importance features are missing",
+                self.id(),
+                "Prediction may proceed",
+                0,
+            );
+        }
+        let names: Vec<_> = missing.iter().map(|(name, _, _)| name.as_str()).collect();
+
+        CounterfactualExplanation::new(
+            "Sepsis Risk Prediction",
+            format!("Missing high-importance features: {:?}", names),
+            self.id(),
+            format!("Obtain {} first; if {} were available, prediction would proceed",
+                    names[0], names.join(", ")),
+            8,
+        )
+        .with_context(
+            "missing_weights",
+            missing.iter()
+                .map(|(name, weight, tolerance)| format!("{}={:.2} (tolerance {:.2})", name, weight, tolerance))
+                .collect::<Vec<_>>()
+                .join(", "),
+        )
+    }
+}
+
+/// Summary of a registered rule, for audits and admin views
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RuleInfo {
+    pub id: String,
+    pub description: String,
+}
+
 /// Main Ethos Guard that checks all rules
 pub struct EthosGuard {
     rules: Vec<Box<dyn EthosRule>>,
@@ -258,6 +347,28 @@ impl EthosGuard {
         self.rules.push(rule);
     }
 
+    /// List the active rules in evaluation order
+    pub fn rules(&self) -> Vec<RuleInfo> {
+        self.rules
+            .iter()
+            .map(|rule| RuleInfo {
+                id: rule.id().to_string(),
+                description: rule.description().to_string(),
+            })
+            .collect()
+    }
+
+    pub fn has_rule(&self, id: &str) -> bool {
+        self.rules.iter().any(|rule| rule.id() == id)
+    }
+
+    /// Disable every rule with the given ID, returning whether any was removed
+    pub fn remove_rule(&mut self, id: &str) -> bool {
+        let before = self.rules.len();
+        self.rules.retain(|rule| rule.id() != id);
+        self.rules.len() != before
+    }
+
     /// Check all rules and return the first violation if any
     pub fn check<T>(&self, data: &PatientData, action: T) -> EthosResult<T> {
         for rule in &self.rules {
@@ -318,4 +429,49 @@ mod tests {
         assert!(explanation.counterfactual.contains("HR"));
         assert!(explanation.counterfactual.contains("SpO2"));
     }
+
+    #[test]
+    fn test_rule_introspection() {
+        let mut guard = EthosGuard::clinical_default();
+        let ids: Vec<_> = guard.rules().into_iter().map(|r| r.id).collect();
+        assert_eq!(ids, vec!["ETHOS-001", "ETHOS-002"]);
+        assert!(guard.has_rule("ETHOS-002"));
+
+        assert!(guard.remove_rule("ETHOS-002"));
+        assert!(!guard.has_rule("ETHOS-002"));
+        assert!(!guard.remove_rule("ETHOS-002"));
+    }
+
+    #[test]
+    fn test_critical_feature_missing_rule() {
+        let rule = CriticalFeatureMissingRule::new(vec![("Lactate", 0.9, 0.5), ("Height", 0.1, 0.5)]);
+        let mut data = PatientData::new();
+        data.set_lab("Lactate", Some(2.4));
+
+        // Missing a low-importance feature passes
+        assert!(rule.check(&data));
+
+        // Missing a high-importance feature blocks and names it
+        data.set_lab("Lactate", None);
+        data.set_vital("Height", Some(180.0));
+        assert!(!rule.check(&data));
+        let explanation = rule.explain(&data);
+        assert!(explanation.counterfactual.starts_with("Obtain Lactate"));
+    }
+
+    #[test]
+    fn test_critical_features_use_their_own_tolerance() {
+        // Same weight, but only Lactate's tolerance is below it
+        let rule = CriticalFeatureMissingRule::new(vec![("Lactate", 0.6, 0.2), ("WBC", 0.6, 0.8)]);
+        let mut data = PatientData::new();
+        data.set_lab("Lactate", Some(2.4));
+        data.set_lab("WBC", None);
+        assert!(rule.check(&data));
+        assert_eq!(rule.explain(&data).counterfactual, "Prediction may proceed");
+
+        data.set_lab("Lactate", None);
+        assert!(!rule.check(&data));
+        assert!(rule.explain(&data).rule_violated.contains("Lactate"));
+        assert!(!rule.explain(&data).rule_violated.contains("WBC"));
+    }
 }
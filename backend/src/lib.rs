@@ -0,0 +1,14 @@
+//! ICU sepsis causal discovery: data loading, mRMR/SURD analysis and graph export
+//!
+//! The `icu_sepsis_backend` binary drives these modules from the command line;
+//! the Python bindings link against them directly.
+
+pub mod config;
+pub mod error;
+pub mod data;
+pub mod causality;
+pub mod context;
+pub mod utils;
+pub mod ethos;
+pub mod visualization;
+pub mod report;
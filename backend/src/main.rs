@@ -1,18 +1,15 @@
-mod config;
-mod data;
-mod causality;
-mod context;
-mod utils;
-mod ethos;
-mod visualization;
-
 use anyhow::Result;
 use clap::Parser;
 use tracing::{info, error, warn};
-use crate::config::Config;
-use crate::data::DataLoader;
-use crate::causality::CausalDiscovery;
-use crate::visualization::CausalGraph;
+use icu_sepsis_backend::config;
+use icu_sepsis_backend::config::Config;
+use icu_sepsis_backend::data::DataLoader;
+use icu_sepsis_backend::causality::{CausalDiscovery, DiscretizationStrategy, MrmrOptions, RankingComparison, SurdDualResult, SurdOptions};
+use icu_sepsis_backend::visualization::CausalGraph;
+use icu_sepsis_backend::report::{AnalysisReport, ConfigSummary};
+use icu_sepsis_backend::utils::table;
+use icu_sepsis_backend::utils::tensor_adapter::TensorAdapter;
+use std::time::Instant;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about = "Deep Causality ICU Sepsis Causal Discovery Engine")]
@@ -32,6 +29,62 @@ struct Args {
     /// Export results to JSON file
     #[arg(long)]
     export_json: Option<String>,
+
+    /// Cache the converted CausalTensor at this path and reuse it while fresh
+    #[arg(long)]
+    tensor_cache: Option<String>,
+
+    /// Compare mRMR selection against a second configuration file
+    #[arg(long)]
+    compare: Option<String>,
+
+    /// Write a structured JSON report of the whole run to this path
+    #[arg(long)]
+    json_report: Option<String>,
+
+    /// Override the config's bin count for mRMR and SURD discretization
+    #[arg(long)]
+    n_bins: Option<usize>,
+
+    /// Override the config's discretization strategy (equal_width, equal_frequency or kmeans)
+    #[arg(long)]
+    discretization: Option<DiscretizationStrategy>,
+
+    /// Print the ranking table without ANSI styling (implied when stdout is not a terminal)
+    #[arg(long, default_value = "false")]
+    no_color: bool,
+}
+
+impl Args {
+    /// Options that change what a run does beyond the config file, in `--flag[=value]` form
+    fn cli_options(&self) -> Vec<String> {
+        let mut options = Vec::new();
+        if self.surd_analysis {
+            options.push("--surd-analysis".to_string());
+        }
+        if self.no_color {
+            options.push("--no-color".to_string());
+        }
+        if let Some(n_bins) = self.n_bins {
+            options.push(format!("--n-bins={}", n_bins));
+        }
+        if let Some(strategy) = self.discretization {
+            options.push(format!("--discretization={}", strategy));
+        }
+        let valued = [
+            ("--export-graph", &self.export_graph),
+            ("--export-json", &self.export_json),
+            ("--tensor-cache", &self.tensor_cache),
+            ("--compare", &self.compare),
+            ("--json-report", &self.json_report),
+        ];
+        for (flag, value) in valued {
+            if let Some(value) = value {
+                options.push(format!("{}={}", flag, value));
+            }
+        }
+        options
+    }
 }
 
 #[tokio::main]
@@ -43,34 +96,95 @@ async fn main() -> Result<()> {
     info!("  Deep Causality ICU Sepsis Backend");
     info!("========================================");
     
-    let config = Config::load(&args.config)?;
+    let mut config = Config::load(&args.config)?;
+    if let Some(n_bins) = args.n_bins {
+        config.causality.n_bins = n_bins;
+    }
+    if let Some(strategy) = args.discretization {
+        config.causality.discretization = Some(strategy);
+    }
+    let mut report = AnalysisReport::new(&args.config, &config);
+    report.set_cli_options(args.cli_options());
+    log_effective_config(&report.config);
+
+    // Set when mRMR fails or selects nothing; reported as a failing exit once the run report is written
+    let mut selection_error = None;
 
     // 1. Load Main Dataset
     info!("Loading training data from {}", config.data.train_path);
-    match DataLoader::load_parquet(&config.data.train_path) {
-        Ok(df) => {
+    let started = Instant::now();
+    // Resolved once, so the tensor cache is checked against the files actually loaded
+    let loaded = DataLoader::expand_glob(&config.data.train_path)
+        .and_then(|files| DataLoader::load_parquet_files(&files).map(|df| (files, df)));
+    report.record_timing("load_data", started.elapsed());
+    match loaded {
+        Ok((train_files, df)) => {
             info!("Data loaded successfully. Shape: {:?}", df.shape());
+            report.data_shape = Some(df.shape());
+
+            let experiment = &config.experiment;
+            DataLoader::validate_schema(
+                &df,
+                &[experiment.patient_id_column.as_str()],
+                &[experiment.target_column.as_str(), experiment.time_column.as_str()],
+            )?;
             
             // 2. Run mRMR Feature Selection
             info!("\n--- mRMR Feature Selection ---");
-            let features = match CausalDiscovery::run_mrmr(&df, &config.experiment.target_column, config.causality.max_features) {
-                Ok(features) => {
-                    info!("Top {} Selected Features:", features.len());
-                    for (i, (name, score)) in features.iter().enumerate() {
-                        info!("  {}. {} (score: {:.4})", i + 1, name, score);
-                    }
-                    features
-                },
+            let started = Instant::now();
+            let mrmr_options = mrmr_options(&config.causality);
+            let mrmr_result = match &args.tensor_cache {
+                Some(cache_path) => TensorAdapter::load_or_build_cache(cache_path, &train_files, &df)
+                    .and_then(|(tensor, names, _)| {
+                        CausalDiscovery::run_mrmr_tensor(&tensor, &names, &config.experiment.target_column, &mrmr_options)
+                    }),
+                None => CausalDiscovery::run_mrmr(&df, &config.experiment.target_column, &mrmr_options),
+            };
+            let features = match mrmr_result {
+                Ok(features) => features,
                 Err(e) => {
                     error!("mRMR Feature Selection failed: {}", e);
+                    selection_error = Some(e);
                     vec![]
                 }
             };
+            let normalized = config.causality.normalize_scores.then(|| CausalDiscovery::normalize_scores(&features));
+            if !features.is_empty() {
+                info!("Top {} Selected Features:", features.len());
+                for (i, (name, score)) in features.iter().enumerate() {
+                    match &normalized {
+                        Some(normalized) => info!("  {}. {} (score: {:.4}, normalized: {:.4})", i + 1, name, score, normalized[i].1),
+                        None => info!("  {}. {} (score: {:.4})", i + 1, name, score),
+                    }
+                }
+                // The log lines are for machines; humans get an aligned table on stdout
+                print!("{}", table::render_rankings(
+                    &features,
+                    normalized.as_deref(),
+                    table::terminal_width(),
+                    table::use_color(args.no_color),
+                ));
+            }
+            report.record_timing("mrmr", started.elapsed());
+            report.set_selected_features(&features);
+            if let Some(normalized) = &normalized {
+                report.set_normalized_scores(normalized);
+            }
+            // A failed selection already carries its own error; an empty one is reported here
+            if selection_error.is_none() {
+                if let Err(e) = CausalDiscovery::ensure_selected(&features, &config.experiment.target_column) {
+                    warn!("========================================");
+                    warn!("  {}", e);
+                    warn!("========================================");
+                    selection_error = Some(e);
+                }
+            }
 
-            // 3. Export causal graph if requested
-            if let Some(graph_path) = &args.export_graph {
+            // 3. Export causal graph if requested (an empty graph would only mislead)
+            if let (Some(graph_path), None) = (&args.export_graph, &selection_error) {
                 info!("\n--- Exporting Causal Graph ---");
-                let graph = CausalGraph::from_mrmr_results(&features, &config.experiment.target_column);
+                // Normalized scores keep edge weights comparable across runs
+                let graph = CausalGraph::from_mrmr_results(normalized.as_ref().unwrap_or(&features), &config.experiment.target_column);
                 graph.write_dot(graph_path)?;
                 info!("Graph exported to {}", graph_path);
                 
@@ -80,10 +194,32 @@ async fn main() -> Result<()> {
                 info!("Graph JSON exported to {}", json_path);
             }
 
-            // 4. Run SURD Dual Analysis if requested
+            // 4. Compare against a second configuration if requested
+            if let Some(compare_path) = &args.compare {
+                info!("\n--- Configuration Comparison ---");
+                let started = Instant::now();
+                let compare_config = Config::load(compare_path)?;
+                report.comparison = Some(run_config_comparison(&config, &compare_config, &df, &features)?);
+                report.record_timing("comparison", started.elapsed());
+            }
+
+            // 5. Run SURD Dual Analysis if requested
             if args.surd_analysis {
                 info!("\n--- SURD Dual Analysis ---");
-                run_surd_dual_analysis(&config).await?;
+                let started = Instant::now();
+                report.surd = run_surd_dual_analysis(&config).await?;
+                report.record_timing("surd", started.elapsed());
+
+                // The mRMR graph stays the default export; SURD gets its own files alongside it
+                if let (Some(graph_path), Some(result)) = (&args.export_graph, &report.surd) {
+                    let stem = graph_path.strip_suffix(".dot").unwrap_or(graph_path);
+                    let graph = CausalGraph::from_surd_result(result, &config.experiment.target_column);
+                    let dot_path = format!("{}_surd.dot", stem);
+                    let json_path = format!("{}_surd.json", stem);
+                    graph.write_dot(&dot_path)?;
+                    std::fs::write(&json_path, graph.to_json()?)?;
+                    info!("SURD graph exported to {} and {}", dot_path, json_path);
+                }
             }
         },
         Err(e) => {
@@ -91,6 +227,15 @@ async fn main() -> Result<()> {
         }
     }
 
+    if let Some(report_path) = &args.json_report {
+        report.write(report_path)?;
+        info!("Run report written to {}", report_path);
+    }
+
+    if let Some(e) = selection_error {
+        return Err(e.into());
+    }
+
     info!("\n========================================");
     info!("  Analysis Complete");
     info!("========================================");
@@ -98,35 +243,118 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
-async fn run_surd_dual_analysis(config: &Config) -> Result<()> {
+/// Log the resolved configuration so every run records its own parameters
+fn log_effective_config(summary: &ConfigSummary) {
+    info!("Effective configuration (from {}):", summary.config_path);
+    info!("  target_column:          {}", summary.target_column);
+    info!("  max_features:           {}", summary.max_features);
+    info!("  min_score:              {}", summary.min_score.map_or("none".to_string(), |s| s.to_string()));
+    info!("  normalize_scores:       {}", summary.normalize_scores);
+    info!("  significance_threshold: {}", summary.significance_threshold);
+    info!("  train_path:             {}", summary.train_path);
+    info!("  sepsis_subset_path:     {}", summary.sepsis_subset_path);
+    info!("  non_sepsis_subset_path: {}", summary.non_sepsis_subset_path);
+    info!("  discretization:         {:?} ({} bins)", summary.discretization, summary.n_bins);
+    info!("  min_samples_per_bin:    {}", summary.min_samples_per_bin);
+    info!("  surd_score_weights:     {:?}", summary.surd_score_weights);
+    info!("  surd_skip_failures:     {}", summary.surd_skip_failures);
+    info!("  cli_options:            {:?}", summary.cli_options);
+}
+
+/// mRMR options described by a `[causality]` config section
+fn mrmr_options(causality: &config::CausalityConfig) -> MrmrOptions {
+    let mut options = MrmrOptions::new(causality.max_features);
+    if let Some(discretizer) = causality.discretizer() {
+        options = options.with_discretizer(discretizer);
+    }
+    match causality.min_score {
+        Some(min_score) => options.with_min_score(min_score),
+        None => options,
+    }
+}
+
+fn run_config_comparison(
+    config: &Config,
+    compare_config: &Config,
+    df: &polars::prelude::DataFrame,
+    primary_features: &[(String, f64)],
+) -> Result<RankingComparison> {
+    // Only reload data when the comparison config points somewhere else
+    let compare_df = if compare_config.data.train_path == config.data.train_path {
+        df.clone()
+    } else {
+        DataLoader::load_parquet_glob(&compare_config.data.train_path)?
+    };
+
+    let compare_features = CausalDiscovery::run_mrmr(
+        &compare_df,
+        &compare_config.experiment.target_column,
+        &mrmr_options(&compare_config.causality),
+    )?;
+
+    let comparison = CausalDiscovery::compare_rankings(primary_features, &compare_features);
+
+    info!("{:<24} {:>8} {:>8} {:>10} {:>10}", "Feature", "Primary", "Compare", "P.Score", "C.Score");
+    for change in &comparison.changes {
+        let rank = |r: Option<usize>| r.map_or("-".to_string(), |r| r.to_string());
+        let score = |s: Option<f64>| s.map_or("-".to_string(), |s| format!("{:.4}", s));
+        info!(
+            "{:<24} {:>8} {:>8} {:>10} {:>10}",
+            change.name,
+            rank(change.primary_rank),
+            rank(change.compare_rank),
+            score(change.primary_score),
+            score(change.compare_score),
+        );
+    }
+    info!("Only in primary: {:?}", comparison.only_primary);
+    info!("Only in comparison: {:?}", comparison.only_compare);
+    info!("Feature overlap (Jaccard): {:.4}", comparison.feature_overlap);
+
+    std::fs::write("../notes/config_comparison.json", serde_json::to_string_pretty(&comparison)?)?;
+    info!("Comparison results saved to notes/config_comparison.json");
+
+    Ok(comparison)
+}
+
+/// Returns the SURD result, or `None` when it failed and the mRMR comparison ran instead
+async fn run_surd_dual_analysis(config: &Config) -> Result<Option<SurdDualResult>> {
     // Load Sepsis subset
     info!("Loading Sepsis subset from {}", config.data.sepsis_subset_path);
-    let sepsis_df = match DataLoader::load_parquet(&config.data.sepsis_subset_path) {
+    let sepsis_df = match DataLoader::load_parquet_glob(&config.data.sepsis_subset_path) {
         Ok(df) => {
             info!("Sepsis subset loaded: {} rows", df.height());
             df
         },
         Err(e) => {
             error!("Failed to load Sepsis subset: {}", e);
-            return Err(e);
+            return Err(e.into());
         }
     };
 
     // Load Non-Sepsis subset
     info!("Loading Non-Sepsis subset from {}", config.data.non_sepsis_subset_path);
-    let non_sepsis_df = match DataLoader::load_parquet(&config.data.non_sepsis_subset_path) {
+    let non_sepsis_df = match DataLoader::load_parquet_glob(&config.data.non_sepsis_subset_path) {
         Ok(df) => {
             info!("Non-Sepsis subset loaded: {} rows", df.height());
             df
         },
         Err(e) => {
             error!("Failed to load Non-Sepsis subset: {}", e);
-            return Err(e);
+            return Err(e.into());
         }
     };
 
     // Run SURD Dual Analysis
-    match CausalDiscovery::run_surd_dual(&sepsis_df, &non_sepsis_df, &config.experiment.target_column) {
+    let mut surd_options = SurdOptions::new()
+        .with_min_samples_per_bin(config.causality.min_samples_per_bin)
+        .with_score_weights(config.causality.surd_score_weights)
+        .with_skip_failures(config.causality.surd_skip_failures);
+    if let Some(discretizer) = config.causality.discretizer() {
+        surd_options = surd_options.with_discretizer(discretizer);
+    }
+
+    match CausalDiscovery::run_surd_dual(&sepsis_df, &non_sepsis_df, &config.experiment.target_column, &surd_options) {
         Ok(result) => {
             info!("\n=== SURD Dual Analysis Results ===\n");
             
@@ -135,12 +363,18 @@ async fn run_surd_dual_analysis(config: &Config) -> Result<()> {
             info!("  Unique (discriminative): {:.4} bits", result.sepsis_result.unique_info);
             info!("  Synergistic (combined):  {:.4} bits", result.sepsis_result.synergistic_info);
             info!("  Total Information:       {:.4} bits", result.sepsis_result.total_info);
+            if let Some(reliability) = result.sepsis_result.estimation_reliability {
+                info!("  Estimation Reliability:  {:.1}%", reliability * 100.0);
+            }
             
             info!("\nNON-SEPSIS Subset Information Decomposition:");
             info!("  Redundant (shared):     {:.4} bits", result.non_sepsis_result.redundant_info);
             info!("  Unique (discriminative): {:.4} bits", result.non_sepsis_result.unique_info);
             info!("  Synergistic (combined):  {:.4} bits", result.non_sepsis_result.synergistic_info);
             info!("  Total Information:       {:.4} bits", result.non_sepsis_result.total_info);
+            if let Some(reliability) = result.non_sepsis_result.estimation_reliability {
+                info!("  Estimation Reliability:  {:.1}%", reliability * 100.0);
+            }
             
             info!("\n=== Causal Driver Comparison ===");
             info!("Sepsis-Specific Drivers (disjoint): {:?}", result.disjoint_drivers);
@@ -151,22 +385,22 @@ async fn run_surd_dual_analysis(config: &Config) -> Result<()> {
             let json_output = serde_json::to_string_pretty(&result)?;
             std::fs::write("../notes/surd_results.json", &json_output)?;
             info!("\nResults exported to notes/surd_results.json");
+            Ok(Some(result))
         },
         Err(e) => {
             warn!("SURD Dual Analysis encountered an error: {}", e);
             warn!("Falling back to mRMR comparison.");
             run_mrmr_comparison(&sepsis_df, &non_sepsis_df, &config.experiment.target_column)?;
+            Ok(None)
         }
     }
-
-    Ok(())
 }
 
 fn run_mrmr_comparison(sepsis_df: &polars::prelude::DataFrame, non_sepsis_df: &polars::prelude::DataFrame, target_col: &str) -> Result<()> {
     info!("\n--- mRMR Feature Comparison (Sepsis vs Non-Sepsis) ---\n");
     
-    let sepsis_features = CausalDiscovery::run_mrmr(sepsis_df, target_col, 10)?;
-    let non_sepsis_features = CausalDiscovery::run_mrmr(non_sepsis_df, target_col, 10)?;
+    let sepsis_features = CausalDiscovery::run_mrmr(sepsis_df, target_col, &MrmrOptions::new(10))?;
+    let non_sepsis_features = CausalDiscovery::run_mrmr(non_sepsis_df, target_col, &MrmrOptions::new(10))?;
 
     info!("SEPSIS Top Features:");
     for (i, (name, score)) in sepsis_features.iter().enumerate() {
@@ -0,0 +1,194 @@
+//! Structured summary of a complete CLI run
+//!
+//! Collects everything the pipeline produces into one JSON document so scripts
+//! and CI jobs don't have to scrape log output.
+
+use crate::causality::{DiscretizationStrategy, RankingComparison, SurdDualResult, SurdScoreWeights};
+use crate::config::Config;
+use crate::error::{DeepCausalityError, Result};
+use serde::{Serialize, Deserialize};
+use std::time::Duration;
+
+/// The effective configuration values that shaped a run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigSummary {
+    pub config_path: String,
+    pub train_path: String,
+    pub sepsis_subset_path: String,
+    pub non_sepsis_subset_path: String,
+    pub target_column: String,
+    pub max_features: usize,
+    pub min_score: Option<f64>,
+    #[serde(default)]
+    pub normalize_scores: bool,
+    pub significance_threshold: f64,
+    pub n_bins: usize,
+    pub discretization: Option<DiscretizationStrategy>,
+    pub min_samples_per_bin: usize,
+    pub surd_score_weights: SurdScoreWeights,
+    pub surd_skip_failures: bool,
+    /// Command-line options given on top of the config file, e.g. `--tensor-cache=cache.bin`
+    #[serde(default)]
+    pub cli_options: Vec<String>,
+}
+
+/// A selected feature and its mRMR score
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureScore {
+    pub name: String,
+    pub score: f64,
+    /// Score relative to the top feature, when normalization was requested
+    #[serde(default)]
+    pub normalized_score: Option<f64>,
+}
+
+/// Wall-clock time spent in one pipeline stage
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StageTiming {
+    pub stage: String,
+    pub seconds: f64,
+}
+
+/// Complete record of a CLI run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisReport {
+    pub config: ConfigSummary,
+    /// (rows, columns) of the training data, if it loaded
+    pub data_shape: Option<(usize, usize)>,
+    pub selected_features: Vec<FeatureScore>,
+    /// Present when the SURD dual analysis ran and succeeded
+    pub surd: Option<SurdDualResult>,
+    /// Present when `--compare` was given
+    pub comparison: Option<RankingComparison>,
+    pub timings: Vec<StageTiming>,
+}
+
+impl AnalysisReport {
+    pub fn new(config_path: &str, config: &Config) -> Self {
+        Self {
+            config: ConfigSummary {
+                config_path: config_path.to_string(),
+                train_path: config.data.train_path.clone(),
+                sepsis_subset_path: config.data.sepsis_subset_path.clone(),
+                non_sepsis_subset_path: config.data.non_sepsis_subset_path.clone(),
+                target_column: config.experiment.target_column.clone(),
+                max_features: config.causality.max_features,
+                min_score: config.causality.min_score,
+                normalize_scores: config.causality.normalize_scores,
+                significance_threshold: config.causality.significance_threshold,
+                n_bins: config.causality.n_bins,
+                discretization: config.causality.discretization,
+                min_samples_per_bin: config.causality.min_samples_per_bin,
+                surd_score_weights: config.causality.surd_score_weights,
+                surd_skip_failures: config.causality.surd_skip_failures,
+                cli_options: Vec::new(),
+            },
+            data_shape: None,
+            selected_features: Vec::new(),
+            surd: None,
+            comparison: None,
+            timings: Vec::new(),
+        }
+    }
+
+    pub fn set_cli_options(&mut self, options: Vec<String>) {
+        self.config.cli_options = options;
+    }
+
+    pub fn set_selected_features(&mut self, features: &[(String, f64)]) {
+        self.selected_features = features.iter()
+            .map(|(name, score)| FeatureScore { name: name.clone(), score: *score, normalized_score: None })
+            .collect();
+    }
+
+    /// Attach normalized scores to the selected features, matched by name
+    pub fn set_normalized_scores(&mut self, normalized: &[(String, f64)]) {
+        for feature in &mut self.selected_features {
+            feature.normalized_score = normalized.iter()
+                .find(|(name, _)| *name == feature.name)
+                .map(|(_, score)| *score);
+        }
+    }
+
+    pub fn record_timing(&mut self, stage: &str, elapsed: Duration) {
+        self.timings.push(StageTiming {
+            stage: stage.to_string(),
+            seconds: elapsed.as_secs_f64(),
+        });
+    }
+
+    /// Write the report as pretty-printed JSON
+    pub fn write(&self, path: &str) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| DeepCausalityError::Export(format!("Failed to serialize analysis report: {}", e)))?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::causality::SurdAnalysisResult;
+
+    fn test_config() -> Config {
+        toml::from_str(r#"
+            [data]
+            train_path = "train.parquet"
+            test_path = "test.parquet"
+            validation_path = "validation.parquet"
+            sepsis_subset_path = "sepsis.parquet"
+            non_sepsis_subset_path = "non_sepsis.parquet"
+
+            [experiment]
+            target_column = "SepsisLabel"
+            patient_id_column = "Patient_ID"
+            time_column = "ICULOS"
+            test_size = 0.2
+            random_seed = 42
+
+            [causality]
+            significance_threshold = 0.05
+            max_features = 10
+        "#).unwrap()
+    }
+
+    #[test]
+    fn test_report_round_trip() {
+        let mut report = AnalysisReport::new("config/default.toml", &test_config());
+        report.set_cli_options(vec!["--surd-analysis".to_string()]);
+        report.data_shape = Some((100, 5));
+        report.set_selected_features(&[("Lactate".to_string(), 0.8), ("MAP".to_string(), 0.4)]);
+        let decomposition = SurdAnalysisResult {
+            redundant_info: 0.1,
+            unique_info: 0.2,
+            synergistic_info: 0.3,
+            total_info: 0.6,
+            estimation_reliability: None,
+            skipped: Vec::new(),
+            per_feature: Default::default(),
+        };
+        report.surd = Some(SurdDualResult {
+            sepsis_result: decomposition.clone(),
+            non_sepsis_result: decomposition,
+            disjoint_drivers: vec!["Lactate".to_string()],
+            shared_drivers: vec!["MAP".to_string()],
+            sepsis_specific_score: 0.0,
+        });
+        report.record_timing("mrmr", Duration::from_millis(1500));
+
+        let json = serde_json::to_string(&report).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        for field in ["config", "data_shape", "selected_features", "surd", "comparison", "timings"] {
+            assert!(value.get(field).is_some(), "missing field {}", field);
+        }
+        assert_eq!(value["config"]["target_column"], "SepsisLabel");
+        assert_eq!(value["config"]["cli_options"][0], "--surd-analysis");
+
+        let parsed: AnalysisReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.selected_features[0].name, "Lactate");
+        assert_eq!(parsed.surd.unwrap().disjoint_drivers, vec!["Lactate"]);
+        assert!(parsed.comparison.is_none());
+        assert!((parsed.timings[0].seconds - 1.5).abs() < 1e-12);
+    }
+}
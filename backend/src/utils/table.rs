@@ -0,0 +1,132 @@
+//! Plain-text tables for human-readable console output
+
+use std::io::IsTerminal;
+
+/// Width used when the terminal size is unknown (e.g. output is piped)
+const DEFAULT_WIDTH: usize = 100;
+/// Narrowest the feature column is squeezed to before the table overflows instead
+const MIN_NAME_WIDTH: usize = 8;
+
+/// Terminal width from `COLUMNS`, then the size of the attached terminal, falling back
+/// to a sensible default
+pub fn terminal_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.parse().ok())
+        .or_else(|| terminal_size::terminal_size().map(|(terminal_size::Width(w), _)| w as usize))
+        .filter(|&w| w > 0)
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+/// Whether ANSI styling should be used: stdout is a terminal and color wasn't disabled
+pub fn use_color(no_color: bool) -> bool {
+    !no_color && std::io::stdout().is_terminal()
+}
+
+/// Render mRMR rankings as an aligned table (rank, feature, score[, normalized]).
+/// Numbers are right-aligned; feature names that don't fit `max_width` are truncated.
+pub fn render_rankings(
+    features: &[(String, f64)],
+    normalized: Option<&[(String, f64)]>,
+    max_width: usize,
+    color: bool,
+) -> String {
+    let mut headers = vec!["Rank", "Feature", "Score"];
+    if normalized.is_some() {
+        headers.push("Normalized");
+    }
+
+    let rows: Vec<Vec<String>> = features.iter()
+        .enumerate()
+        .map(|(i, (name, score))| {
+            let mut row = vec![(i + 1).to_string(), name.clone(), format!("{:.4}", score)];
+            if let Some(normalized) = normalized {
+                row.push(normalized.get(i).map_or("-".to_string(), |(_, n)| format!("{:.4}", n)));
+            }
+            row
+        })
+        .collect();
+
+    let mut widths: Vec<usize> = headers.iter().map(|h| h.chars().count()).collect();
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    // Give the feature column whatever the numeric columns and separators leave over
+    let fixed: usize = widths.iter().enumerate().filter(|&(i, _)| i != 1).map(|(_, w)| w).sum::<usize>()
+        + 2 * (widths.len() - 1);
+    widths[1] = widths[1].min(max_width.saturating_sub(fixed).max(MIN_NAME_WIDTH));
+
+    let line = |cells: &[String]| {
+        cells.iter()
+            .zip(&widths)
+            .enumerate()
+            .map(|(i, (cell, &width))| match i {
+                1 => format!("{:<width$}", truncate(cell, width)),
+                _ => format!("{:>width$}", cell),
+            })
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let header = line(&headers.iter().map(|h| h.to_string()).collect::<Vec<_>>());
+    let rule = "-".repeat(header.chars().count());
+    let mut table = if color {
+        format!("\x1b[1m{}\x1b[0m\n", header)
+    } else {
+        format!("{}\n", header)
+    };
+    table.push_str(&rule);
+    table.push('\n');
+    for row in &rows {
+        table.push_str(&line(row));
+        table.push('\n');
+    }
+    table
+}
+
+/// Cut `text` to `width` characters, marking the cut with an ellipsis
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        return text.to_string();
+    }
+    let kept: String = text.chars().take(width.saturating_sub(1)).collect();
+    format!("{}…", kept)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_rankings_aligns_columns() {
+        let features = vec![
+            ("ICULOS".to_string(), 262.1234),
+            ("HR".to_string(), 15.5),
+            ("Bilirubin_total".to_string(), 3.25),
+        ];
+        let normalized = vec![
+            ("ICULOS".to_string(), 1.0),
+            ("HR".to_string(), 0.0591),
+            ("Bilirubin_total".to_string(), 0.0124),
+        ];
+
+        let table = render_rankings(&features, Some(&normalized), 100, false);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert_eq!(lines[0], "Rank  Feature             Score  Normalized");
+        assert_eq!(lines[2], "   1  ICULOS           262.1234      1.0000");
+        assert_eq!(lines[3], "   2  HR                15.5000      0.0591");
+        // Right-aligned numbers end in the same column on every row
+        assert!(lines.iter().all(|l| l.chars().count() == lines[0].chars().count()));
+
+        // A narrow terminal truncates the feature names, not the numbers
+        let narrow = render_rankings(&features, None, 24, false);
+        let row = narrow.lines().nth(4).unwrap();
+        assert_eq!(row, "   3  Bilirub…    3.2500");
+    }
+}
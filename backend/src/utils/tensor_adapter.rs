@@ -1,22 +1,42 @@
 use polars::prelude::*;
 use deep_causality_tensor::CausalTensor;
-use anyhow::{Result, Context};
+use crate::error::{DeepCausalityError, Result};
+use rand::prelude::*;
+use rand::rngs::StdRng;
+use serde::{Serialize, Deserialize};
+use std::io::{BufReader, BufWriter};
+use tracing::{info, warn};
 
 pub struct TensorAdapter;
 
+/// Most rows `repeat_rows_by_weight` may produce
+const MAX_WEIGHTED_ROWS: usize = 50_000_000;
+
+/// On-disk form of a converted tensor, used to skip re-conversion between runs
+#[derive(Serialize, Deserialize)]
+struct TensorCache {
+    shape: Vec<usize>,
+    data: Vec<Option<f64>>,
+    column_names: Vec<String>,
+}
+
 impl TensorAdapter {
     pub fn df_to_tensor(df: &DataFrame) -> Result<(CausalTensor<Option<f64>>, Vec<String>)> {
         let (height, width) = df.shape();
         let mut flat_data: Vec<Option<f64>> = Vec::with_capacity(height * width);
         let mut column_names: Vec<String> = Vec::with_capacity(width);
 
+        let conversion = |col_name: &str, e: PolarsError| {
+            DeepCausalityError::TensorConversion(format!("Column {} is not numeric: {}", col_name, e))
+        };
+
         for col_name in df.get_column_names() {
             let series = df.column(col_name)?;
             column_names.push(col_name.to_string());
 
             // Cast to Float64 and handle nulls
-            let ca = series.cast(&DataType::Float64)?;
-            let f64_ca = ca.f64()?;
+            let ca = series.cast(&DataType::Float64).map_err(|e| conversion(col_name, e))?;
+            let f64_ca = ca.f64().map_err(|e| conversion(col_name, e))?;
 
             for opt_val in f64_ca.into_iter() {
                 flat_data.push(opt_val);
@@ -24,11 +44,210 @@ impl TensorAdapter {
         }
 
         // DeepCausality expects data in Column-Major order based on reference implementation
-        let tensor = CausalTensor::new(flat_data, vec![height, width])
-            .context("Failed to create CausalTensor")?;
+        let tensor = Self::column_major_tensor(flat_data, height, width)?;
 
         Ok((tensor, column_names))
     }
+
+    /// Build a `rows x cols` column-major tensor, checking the value count up front so a
+    /// mismatch is reported with its likely cause rather than as an opaque tensor error
+    pub fn column_major_tensor(data: Vec<Option<f64>>, rows: usize, cols: usize) -> Result<CausalTensor<Option<f64>>> {
+        if data.len() != rows * cols {
+            return Err(DeepCausalityError::TensorConversion(format!(
+                "expected {} values ({} rows x {} columns), got {}; columns of unequal length?",
+                rows * cols, rows, cols, data.len()
+            )));
+        }
+        CausalTensor::new(data, vec![rows, cols])
+            .map_err(|e| DeepCausalityError::TensorConversion(format!("Failed to create CausalTensor: {}", e)))
+    }
+
+    /// (rows, columns) of row-major input, rejecting ragged rows
+    pub fn check_rectangular<T>(rows: &[Vec<T>]) -> Result<(usize, usize)> {
+        let n_cols = rows.first().map_or(0, |row| row.len());
+        if let Some(i) = rows.iter().position(|row| row.len() != n_cols) {
+            let total: usize = rows.iter().map(|row| row.len()).sum();
+            return Err(DeepCausalityError::TensorConversion(format!(
+                "expected {} values ({} rows x {} columns), got {}: row {} has {} values (ragged input)",
+                rows.len() * n_cols, rows.len(), n_cols, total, i, rows[i].len()
+            )));
+        }
+        Ok((rows.len(), n_cols))
+    }
+
+    /// Copy the given rows (in that order, repeats allowed) of a column-major tensor
+    pub fn select_rows(tensor: &CausalTensor<Option<f64>>, rows: &[usize]) -> Result<CausalTensor<Option<f64>>> {
+        let height = tensor.shape()[0];
+        if let Some(&row) = rows.iter().find(|&&row| row >= height) {
+            return Err(DeepCausalityError::TensorConversion(format!(
+                "row {} out of range for a tensor with {} rows", row, height
+            )));
+        }
+        let data: Vec<Option<f64>> = tensor.as_slice()
+            .chunks(height.max(1))
+            .flat_map(|column| rows.iter().map(move |&row| column[row]))
+            .collect();
+        Self::column_major_tensor(data, rows.len(), tensor.shape()[1])
+    }
+
+    /// Stack every chunk in `chunks` under `tensor`, in order, with one copy of each;
+    /// all are column-major with the same columns
+    pub fn append_rows(tensor: &CausalTensor<Option<f64>>, chunks: &[CausalTensor<Option<f64>>]) -> Result<CausalTensor<Option<f64>>> {
+        let width = tensor.shape()[1];
+        if let Some(chunk) = chunks.iter().find(|chunk| chunk.shape()[1] != width) {
+            return Err(DeepCausalityError::TensorConversion(format!(
+                "Got rows with {} columns for a tensor with {}", chunk.shape()[1], width
+            )));
+        }
+        let parts: Vec<(&[Option<f64>], usize)> = std::iter::once(tensor)
+            .chain(chunks)
+            .map(|part| (part.as_slice(), part.shape()[0]))
+            .collect();
+        let height: usize = parts.iter().map(|(_, rows)| rows).sum();
+
+        // Indexed rather than chunked, so an empty part still lines up with every column
+        let mut data: Vec<Option<f64>> = Vec::with_capacity(height * width);
+        for col in 0..width {
+            for (values, rows) in &parts {
+                data.extend_from_slice(&values[col * rows..(col + 1) * rows]);
+            }
+        }
+        Self::column_major_tensor(data, height, width)
+    }
+
+    /// Apply row weights for estimators that only count rows: each row is repeated
+    /// round(weight / smallest positive weight) times and zero-weight rows are dropped.
+    /// Exact for weights in integer ratios (e.g. class weights 1 and 12).
+    pub fn repeat_rows_by_weight(tensor: &CausalTensor<Option<f64>>, weights: &[f64]) -> Result<CausalTensor<Option<f64>>> {
+        let height = tensor.shape()[0];
+        if weights.len() != height {
+            return Err(DeepCausalityError::TensorConversion(format!(
+                "Got {} sample weights for {} rows", weights.len(), height
+            )));
+        }
+        if weights.iter().any(|w| !w.is_finite() || *w < 0.0) {
+            return Err(DeepCausalityError::TensorConversion("Sample weights must be finite and non-negative".into()));
+        }
+        let Some(unit) = weights.iter().copied().filter(|w| *w > 0.0).reduce(f64::min) else {
+            return Err(DeepCausalityError::TensorConversion("Sample weights are all zero".into()));
+        };
+
+        let repeats: Vec<usize> = weights.iter().map(|w| (w / unit).round() as usize).collect();
+        let total: usize = repeats.iter().sum();
+        if total > MAX_WEIGHTED_ROWS {
+            return Err(DeepCausalityError::TensorConversion(format!(
+                "Sample weights would expand the data to {} rows (limit {}); use coarser weight ratios",
+                total, MAX_WEIGHTED_ROWS
+            )));
+        }
+        let rows: Vec<usize> = repeats.iter()
+            .enumerate()
+            .flat_map(|(row, &n)| std::iter::repeat(row).take(n))
+            .collect();
+        Self::select_rows(tensor, &rows)
+    }
+
+    /// Draw `n` rows at random. Without replacement the rows keep their original
+    /// order; the same `seed` always draws the same rows, `None` seeds from entropy.
+    pub fn sample_rows(
+        tensor: &CausalTensor<Option<f64>>,
+        n: usize,
+        with_replacement: bool,
+        seed: Option<u64>,
+    ) -> Result<CausalTensor<Option<f64>>> {
+        let height = tensor.shape()[0];
+        let mut rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+
+        let rows: Vec<usize> = if with_replacement {
+            if height == 0 && n > 0 {
+                return Err(DeepCausalityError::TensorConversion("Cannot sample from an empty tensor".into()));
+            }
+            (0..n).map(|_| rng.gen_range(0..height)).collect()
+        } else {
+            if n > height {
+                return Err(DeepCausalityError::TensorConversion(format!(
+                    "Cannot sample {} of {} rows without replacement", n, height
+                )));
+            }
+            let mut rows = rand::seq::index::sample(&mut rng, height, n).into_vec();
+            rows.sort_unstable();
+            rows
+        };
+        Self::select_rows(tensor, &rows)
+    }
+
+    /// Serialize a tensor and its column names to a compact binary file
+    pub fn save_tensor(tensor: &CausalTensor<Option<f64>>, column_names: &[String], path: &str) -> Result<()> {
+        let cache = TensorCache {
+            shape: tensor.shape().to_vec(),
+            data: tensor.as_slice().to_vec(),
+            column_names: column_names.to_vec(),
+        };
+
+        let file = std::fs::File::create(path).map_err(|e| {
+            DeepCausalityError::TensorConversion(format!("Failed to create tensor cache {}: {}", path, e))
+        })?;
+        bincode::serialize_into(BufWriter::new(file), &cache).map_err(|e| {
+            DeepCausalityError::TensorConversion(format!("Failed to write tensor cache {}: {}", path, e))
+        })?;
+        Ok(())
+    }
+
+    /// Load a tensor and its column names written by `save_tensor`
+    pub fn load_tensor(path: &str) -> Result<(CausalTensor<Option<f64>>, Vec<String>)> {
+        let file = std::fs::File::open(path).map_err(|e| {
+            DeepCausalityError::TensorConversion(format!("Failed to open tensor cache {}: {}", path, e))
+        })?;
+        let cache: TensorCache = bincode::deserialize_from(BufReader::new(file)).map_err(|e| {
+            DeepCausalityError::TensorConversion(format!("Failed to read tensor cache {}: {}", path, e))
+        })?;
+
+        let tensor = CausalTensor::new(cache.data, cache.shape).map_err(|e| {
+            DeepCausalityError::TensorConversion(format!("Failed to rebuild CausalTensor from cache: {}", e))
+        })?;
+        Ok((tensor, cache.column_names))
+    }
+
+    /// A cache is fresh when it was written after every source file was last modified.
+    /// Sources are resolved file paths (e.g. from `DataLoader::expand_glob`), not patterns.
+    pub fn is_cache_fresh(cache_path: &str, source_paths: &[String]) -> bool {
+        let modified = |path: &str| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+        let newest_source = source_paths.iter()
+            .map(|path| modified(path))
+            .collect::<Option<Vec<_>>>()
+            .and_then(|times| times.into_iter().max());
+        match (modified(cache_path), newest_source) {
+            (Some(cache), Some(source)) => cache >= source,
+            _ => false,
+        }
+    }
+
+    /// Load the tensor cache when it is fresh for `source_paths` and matches `df`, otherwise
+    /// convert `df` and rewrite the cache. The flag is true when the cache was used.
+    pub fn load_or_build_cache(
+        cache_path: &str,
+        source_paths: &[String],
+        df: &DataFrame,
+    ) -> Result<(CausalTensor<Option<f64>>, Vec<String>, bool)> {
+        if Self::is_cache_fresh(cache_path, source_paths) {
+            match Self::load_tensor(cache_path) {
+                Ok((tensor, names)) if names == df.get_column_names() && tensor.shape() == [df.height(), df.width()] => {
+                    info!("Loaded CausalTensor from cache {}", cache_path);
+                    return Ok((tensor, names, true));
+                }
+                Ok(_) => warn!("Tensor cache {} does not match the input data; rebuilding", cache_path),
+                Err(e) => warn!("Failed to read tensor cache {}: {}; rebuilding", cache_path, e),
+            }
+        }
+
+        let (tensor, names) = Self::df_to_tensor(df)?;
+        Self::save_tensor(&tensor, &names, cache_path)?;
+        info!("Saved CausalTensor cache to {}", cache_path);
+        Ok((tensor, names, false))
+    }
 }
 
 #[cfg(test)]
@@ -49,4 +268,112 @@ mod tests {
         
         Ok(())
     }
+
+    #[test]
+    fn test_seeded_sampling_is_reproducible() -> Result<()> {
+        let values: Vec<Option<f64>> = (0..40).map(|v| Some(v as f64)).collect();
+        let tensor = TensorAdapter::column_major_tensor(values, 20, 2)?;
+
+        let a = TensorAdapter::sample_rows(&tensor, 5, false, Some(7))?;
+        let b = TensorAdapter::sample_rows(&tensor, 5, false, Some(7))?;
+        assert_eq!(a.as_slice(), b.as_slice());
+        assert_eq!(a.shape(), &[5, 2]);
+
+        // Rows stay aligned across columns: the second column is the first plus 20
+        let (first, second) = a.as_slice().split_at(5);
+        for (x, y) in first.iter().zip(second) {
+            assert_eq!(y.unwrap(), x.unwrap() + 20.0);
+        }
+
+        assert!(TensorAdapter::sample_rows(&tensor, 21, false, Some(7)).is_err());
+        assert_eq!(TensorAdapter::sample_rows(&tensor, 30, true, Some(7))?.shape(), &[30, 2]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_weighted_rows_repeat_in_proportion() -> Result<()> {
+        let tensor = TensorAdapter::column_major_tensor(vec![Some(1.0), Some(2.0), Some(3.0), Some(4.0), Some(5.0), None], 3, 2)?;
+        let repeated = TensorAdapter::repeat_rows_by_weight(&tensor, &[0.5, 1.0, 0.0])?;
+        assert_eq!(repeated.shape(), &[3, 2]);
+        assert_eq!(repeated.as_slice(), &[Some(1.0), Some(2.0), Some(2.0), Some(4.0), Some(5.0), Some(5.0)]);
+        assert!(TensorAdapter::repeat_rows_by_weight(&tensor, &[0.0; 3]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_appended_rows_follow_each_column() -> Result<()> {
+        let tensor = TensorAdapter::column_major_tensor(vec![Some(1.0), Some(2.0), Some(10.0), Some(20.0)], 2, 2)?;
+        let rows = TensorAdapter::column_major_tensor(vec![Some(3.0), None], 1, 2)?;
+        let empty = TensorAdapter::column_major_tensor(Vec::new(), 0, 2)?;
+        let grown = TensorAdapter::append_rows(&tensor, &[rows, empty, tensor.clone()])?;
+        assert_eq!(grown.shape(), &[5, 2]);
+        assert_eq!(grown.as_slice(), &[
+            Some(1.0), Some(2.0), Some(3.0), Some(1.0), Some(2.0),
+            Some(10.0), Some(20.0), None, Some(10.0), Some(20.0),
+        ]);
+        let narrow = TensorAdapter::column_major_tensor(vec![Some(1.0)], 1, 1)?;
+        assert!(TensorAdapter::append_rows(&tensor, &[narrow]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_ragged_rows_are_rejected() {
+        let rows = vec![vec![1.0, 2.0, 3.0], vec![4.0, 5.0], vec![6.0, 7.0, 8.0]];
+        let err = TensorAdapter::check_rectangular(&rows).unwrap_err().to_string();
+        assert!(err.contains("expected 9 values"), "{}", err);
+        assert!(err.contains("got 8: row 1 has 2 values (ragged input)"), "{}", err);
+
+        assert_eq!(TensorAdapter::check_rectangular(&rows[..1]).unwrap(), (1, 3));
+        let err = TensorAdapter::column_major_tensor(vec![Some(1.0); 5], 3, 2).unwrap_err().to_string();
+        assert!(err.contains("expected 6 values (3 rows x 2 columns), got 5"), "{}", err);
+    }
+
+    #[test]
+    fn test_tensor_cache_round_trip() -> Result<()> {
+        let df = df! [
+            "a" => [Some(1.0), None],
+            "b" => [Some(3.0), Some(4.0)]
+        ]?;
+        let (tensor, names) = TensorAdapter::df_to_tensor(&df)?;
+
+        let path = std::env::temp_dir().join(format!("tensor_cache_round_trip_{}.bin", std::process::id()));
+        let path = path.to_str().unwrap();
+        TensorAdapter::save_tensor(&tensor, &names, path)?;
+        let (loaded, loaded_names) = TensorAdapter::load_tensor(path)?;
+        std::fs::remove_file(path)?;
+
+        assert_eq!(loaded_names, names);
+        assert_eq!(loaded.shape(), tensor.shape());
+        assert_eq!(loaded.as_slice(), tensor.as_slice());
+        Ok(())
+    }
+
+    #[test]
+    fn test_cache_for_glob_source_is_reused() -> Result<()> {
+        use crate::data::DataLoader;
+
+        let dir = std::env::temp_dir().join(format!("tensor_cache_glob_{}", std::process::id()));
+        std::fs::create_dir_all(&dir)?;
+        for (name, mut df) in [
+            ("part_1.parquet", df!["HR" => [80.0, 90.0], "SepsisLabel" => [0.0, 1.0]]?),
+            ("part_2.parquet", df!["HR" => [70.0], "SepsisLabel" => [0.0]]?),
+        ] {
+            ParquetWriter::new(std::fs::File::create(dir.join(name))?).finish(&mut df)?;
+        }
+        let sources = DataLoader::expand_glob(dir.join("part_*.parquet").to_str().unwrap())?;
+        assert_eq!(sources.len(), 2);
+        let df = DataLoader::load_parquet_files(&sources)?;
+        let cache = dir.join("cache.bin");
+        let cache = cache.to_str().unwrap();
+
+        let (_, _, first_hit) = TensorAdapter::load_or_build_cache(cache, &sources, &df)?;
+        let (tensor, names, second_hit) = TensorAdapter::load_or_build_cache(cache, &sources, &df)?;
+        std::fs::remove_dir_all(&dir)?;
+
+        assert!(!first_hit);
+        assert!(second_hit);
+        assert_eq!(names, vec!["HR", "SepsisLabel"]);
+        assert_eq!(tensor.shape(), &[3, 2]);
+        Ok(())
+    }
 }
@@ -3,7 +3,9 @@
 //! Exports causal graphs to Graphviz DOT format for visualization.
 
 use std::io::Write;
-use anyhow::Result;
+use std::time::{Duration, Instant};
+use crate::causality::SurdDualResult;
+use crate::error::{DeepCausalityError, Result};
 use serde::Serialize;
 
 /// Node in the causal graph
@@ -24,6 +26,13 @@ pub struct CausalEdge {
     pub edge_type: EdgeType,
 }
 
+/// A labelled group of nodes drawn inside one box, e.g. near-duplicate features
+#[derive(Debug, Clone, Serialize)]
+pub struct NodeCluster {
+    pub label: String,
+    pub node_ids: Vec<String>,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, PartialEq)]
 pub enum NodeType {
     /// Feature/variable node
@@ -48,12 +57,114 @@ pub enum EdgeType {
     Association,
 }
 
+/// Color scheme for DOT export
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq)]
+pub enum GraphTheme {
+    /// Light text on a dark navy background (screens, slides)
+    #[default]
+    Dark,
+    /// Dark text on a white background (documents, web pages)
+    Light,
+    /// Black on white with grayscale edges (papers, printing)
+    Print,
+}
+
+impl std::str::FromStr for NodeType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "feature" => Ok(Self::Feature),
+            "target" => Ok(Self::Target),
+            "latent" => Ok(Self::Latent),
+            "mechanism" => Ok(Self::Mechanism),
+            other => Err(format!(
+                "unknown node type '{}' (expected feature, target, latent or mechanism)", other
+            )),
+        }
+    }
+}
+
+impl std::str::FromStr for EdgeType {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "causal" => Ok(Self::Causal),
+            "redundant" => Ok(Self::Redundant),
+            "synergistic" => Ok(Self::Synergistic),
+            "association" => Ok(Self::Association),
+            other => Err(format!(
+                "unknown edge type '{}' (expected causal, redundant, synergistic or association)", other
+            )),
+        }
+    }
+}
+
+impl std::str::FromStr for GraphTheme {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "dark" => Ok(Self::Dark),
+            "light" => Ok(Self::Light),
+            "print" => Ok(Self::Print),
+            other => Err(format!("unknown graph theme '{}' (expected dark, light or print)", other)),
+        }
+    }
+}
+
+/// Colors used by one theme
+struct ThemePalette {
+    background: &'static str,
+    font: &'static str,
+    node_font: &'static str,
+    default_edge: &'static str,
+    /// Fill for Target, Feature, Latent, Mechanism nodes
+    nodes: [&'static str; 4],
+    /// Color for Causal, Redundant, Synergistic, Association edges
+    edges: [&'static str; 4],
+}
+
+impl GraphTheme {
+    fn palette(self) -> ThemePalette {
+        match self {
+            GraphTheme::Dark => ThemePalette {
+                background: "#1a1a2e",
+                font: "white",
+                node_font: "white",
+                default_edge: "#4a4a6a",
+                nodes: ["#e94560", "#0f3460", "#533483", "#16213e"],
+                edges: ["#00ff88", "#ff8800", "#00aaff", "#888888"],
+            },
+            GraphTheme::Light => ThemePalette {
+                background: "#ffffff",
+                font: "#222222",
+                node_font: "#222222",
+                default_edge: "#888888",
+                nodes: ["#f8b4c0", "#cfe2f3", "#e1d5f0", "#e5e8e8"],
+                edges: ["#2e8b57", "#e67e00", "#1f77b4", "#999999"],
+            },
+            GraphTheme::Print => ThemePalette {
+                background: "#ffffff",
+                font: "#000000",
+                node_font: "#000000",
+                default_edge: "#000000",
+                nodes: ["#d9d9d9", "#ffffff", "#f0f0f0", "#bfbfbf"],
+                edges: ["#000000", "#555555", "#333333", "#aaaaaa"],
+            },
+        }
+    }
+}
+
 /// A causal graph structure for visualization
 #[derive(Debug, Clone, Serialize)]
 pub struct CausalGraph {
     pub title: String,
     pub nodes: Vec<CausalNode>,
     pub edges: Vec<CausalEdge>,
+    /// Layout hint: each cluster becomes a Graphviz `subgraph cluster_N` box
+    pub clusters: Vec<NodeCluster>,
 }
 
 impl CausalGraph {
@@ -62,6 +173,7 @@ impl CausalGraph {
             title: title.into(),
             nodes: Vec::new(),
             edges: Vec::new(),
+            clusters: Vec::new(),
         }
     }
 
@@ -104,7 +216,7 @@ impl CausalGraph {
         
         // Add feature nodes with edges to target
         for (name, score) in features {
-            let safe_id = name.replace(' ', "_").replace('-', "_").to_lowercase();
+            let safe_id = Self::node_id(name);
             graph.add_node_with_score(&safe_id, name, NodeType::Feature, *score);
             graph.add_edge(&safe_id, "target", *score, EdgeType::Causal);
         }
@@ -112,16 +224,83 @@ impl CausalGraph {
         graph
     }
 
-    /// Export to DOT format (Graphviz)
+    /// Box the nodes labelled with the given feature names together.
+    /// Names without a node are ignored; a node stays in the first cluster it joins.
+    pub fn add_cluster(&mut self, label: impl Into<String>, features: &[String]) -> &mut Self {
+        let node_ids = self.nodes.iter()
+            .filter(|n| features.contains(&n.label))
+            .filter(|n| !self.clusters.iter().any(|c| c.node_ids.contains(&n.id)))
+            .map(|n| n.id.clone())
+            .collect();
+        self.clusters.push(NodeCluster { label: label.into(), node_ids });
+        self
+    }
+
+    /// Build a graph from a SURD dual analysis.
+    ///
+    /// The sepsis subset's redundant, unique and synergistic terms become mechanism
+    /// nodes feeding the target, weighted by their share of the total information.
+    /// Each feature feeds the mechanisms it holds a share of in the sepsis subset's
+    /// per-feature terms, weighted by that share of the total information.
+    pub fn from_surd_result(result: &SurdDualResult, target: &str) -> Self {
+        let mut graph = Self::new(format!("SURD Decomposition → {}", target));
+        graph.add_node("target", target, NodeType::Target);
+
+        let terms = &result.sepsis_result;
+        let share = |info: f64| if terms.total_info > 0.0 { info / terms.total_info } else { 0.0 };
+        let mechanisms = [
+            ("redundant", "Redundant", share(terms.redundant_info), EdgeType::Redundant),
+            ("unique", "Unique", share(terms.unique_info), EdgeType::Causal),
+            ("synergistic", "Synergistic", share(terms.synergistic_info), EdgeType::Synergistic),
+        ];
+        for (id, label, weight, edge_type) in mechanisms {
+            graph.add_node_with_score(id, label, NodeType::Mechanism, weight);
+            graph.add_edge(id, "target", weight, edge_type);
+        }
+
+        for (name, feature) in &terms.per_feature {
+            let safe_id = Self::node_id(name);
+            graph.add_node(&safe_id, name, NodeType::Feature);
+            for (mechanism, info, edge_type) in [
+                ("redundant", feature.redundant, EdgeType::Redundant),
+                ("unique", feature.unique, EdgeType::Causal),
+                ("synergistic", feature.synergistic, EdgeType::Synergistic),
+            ] {
+                if info > 0.0 {
+                    graph.add_edge(&safe_id, mechanism, share(info), edge_type);
+                }
+            }
+        }
+
+        graph
+    }
+
+    /// DOT-safe node id for a feature name
+    fn node_id(name: &str) -> String {
+        name.replace(' ', "_").replace('-', "_").to_lowercase()
+    }
+
+    /// Text for a quoted DOT string, with backslashes and quotes escaped
+    fn dot_escape(text: &str) -> String {
+        text.replace('\\', "\\\\").replace('"', "\\\"")
+    }
+
+    /// Export to DOT format (Graphviz) using the dark theme
     pub fn to_dot(&self) -> String {
+        self.to_dot_themed(GraphTheme::Dark)
+    }
+
+    /// Export to DOT format (Graphviz) with the given color theme
+    pub fn to_dot_themed(&self, theme: GraphTheme) -> String {
+        let palette = theme.palette();
         let mut dot = String::new();
         
         dot.push_str("digraph CausalGraph {\n");
         dot.push_str("  // Graph settings\n");
         dot.push_str("  rankdir=LR;\n");
-        dot.push_str("  bgcolor=\"#1a1a2e\";\n");
-        dot.push_str("  fontcolor=\"white\";\n");
-        dot.push_str(&format!("  label=\"{}\";\n", self.title));
+        dot.push_str(&format!("  bgcolor=\"{}\";\n", palette.background));
+        dot.push_str(&format!("  fontcolor=\"{}\";\n", palette.font));
+        dot.push_str(&format!("  label=\"{}\";\n", Self::dot_escape(&self.title)));
         dot.push_str("  labelloc=\"t\";\n");
         dot.push_str("  fontname=\"Helvetica\";\n");
         dot.push_str("  fontsize=16;\n");
@@ -132,47 +311,64 @@ impl CausalGraph {
         dot.push_str("    fontname=\"Helvetica\",\n");
         dot.push_str("    fontsize=10,\n");
         dot.push_str("    style=\"filled\",\n");
-        dot.push_str("    fontcolor=\"white\"\n");
+        dot.push_str(&format!("    fontcolor=\"{}\"\n", palette.node_font));
         dot.push_str("  ];\n\n");
         
         dot.push_str("  // Default edge style\n");
         dot.push_str("  edge [\n");
         dot.push_str("    fontname=\"Helvetica\",\n");
         dot.push_str("    fontsize=8,\n");
-        dot.push_str("    color=\"#4a4a6a\"\n");
+        dot.push_str(&format!("    color=\"{}\"\n", palette.default_edge));
         dot.push_str("  ];\n\n");
         
-        // Add nodes
-        dot.push_str("  // Nodes\n");
-        for node in &self.nodes {
+        let node_line = |node: &CausalNode, indent: &str| {
             let (fillcolor, shape) = match node.node_type {
-                NodeType::Target => ("#e94560", "oval"),
-                NodeType::Feature => ("#0f3460", "box"),
-                NodeType::Latent => ("#533483", "diamond"),
-                NodeType::Mechanism => ("#16213e", "hexagon"),
+                NodeType::Target => (palette.nodes[0], "oval"),
+                NodeType::Feature => (palette.nodes[1], "box"),
+                NodeType::Latent => (palette.nodes[2], "diamond"),
+                NodeType::Mechanism => (palette.nodes[3], "hexagon"),
             };
             
             let label = if let Some(score) = node.score {
-                format!("{}\\n({:.3})", node.label, score)
+                format!("{}\\n({:.3})", Self::dot_escape(&node.label), score)
             } else {
-                node.label.clone()
+                Self::dot_escape(&node.label)
             };
             
-            dot.push_str(&format!(
-                "  {} [label=\"{}\", fillcolor=\"{}\", shape={}];\n",
-                node.id, label, fillcolor, shape
-            ));
+            format!(
+                "{}{} [label=\"{}\", fillcolor=\"{}\", shape={}];\n",
+                indent, node.id, label, fillcolor, shape
+            )
+        };
+
+        // Add nodes
+        dot.push_str("  // Nodes\n");
+        let clustered = |node: &CausalNode| self.clusters.iter().any(|c| c.node_ids.contains(&node.id));
+        for node in self.nodes.iter().filter(|n| !clustered(n)) {
+            dot.push_str(&node_line(node, "  "));
         }
         dot.push('\n');
+
+        // Clustered nodes are declared inside their boxes
+        for (i, cluster) in self.clusters.iter().enumerate() {
+            dot.push_str(&format!("  subgraph cluster_{} {{\n", i));
+            dot.push_str(&format!("    label=\"{}\";\n", Self::dot_escape(&cluster.label)));
+            dot.push_str("    style=\"rounded,dashed\";\n");
+            dot.push_str(&format!("    color=\"{}\";\n", palette.default_edge));
+            for node in self.nodes.iter().filter(|n| cluster.node_ids.contains(&n.id)) {
+                dot.push_str(&node_line(node, "    "));
+            }
+            dot.push_str("  }\n\n");
+        }
         
         // Add edges
         dot.push_str("  // Edges\n");
         for edge in &self.edges {
             let color = match edge.edge_type {
-                EdgeType::Causal => "#00ff88",
-                EdgeType::Redundant => "#ff8800",
-                EdgeType::Synergistic => "#00aaff",
-                EdgeType::Association => "#888888",
+                EdgeType::Causal => palette.edges[0],
+                EdgeType::Redundant => palette.edges[1],
+                EdgeType::Synergistic => palette.edges[2],
+                EdgeType::Association => palette.edges[3],
             };
             
             let penwidth = 1.0 + edge.weight * 3.0;
@@ -196,7 +392,28 @@ impl CausalGraph {
 
     /// Export to JSON for web visualization
     pub fn to_json(&self) -> Result<String> {
-        Ok(serde_json::to_string_pretty(&self)?)
+        serde_json::to_string_pretty(&self)
+            .map_err(|e| DeepCausalityError::Export(format!("Failed to serialize graph: {}", e)))
+    }
+
+    /// Render to SVG markup with Graphviz (requires graphviz installed)
+    pub fn to_svg(&self, theme: GraphTheme, timeout: Duration) -> Result<String> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        static RENDERS: AtomicUsize = AtomicUsize::new(0);
+
+        // Unique per process and call, so concurrent renders don't share files
+        let stem = format!("causal_graph_{}_{}", std::process::id(), RENDERS.fetch_add(1, Ordering::Relaxed));
+        let dot_path = std::env::temp_dir().join(format!("{}.dot", stem));
+        let svg_path = std::env::temp_dir().join(format!("{}.svg", stem));
+        std::fs::write(&dot_path, self.to_dot_themed(theme))?;
+
+        let rendered = GraphvizExporter::render(
+            &dot_path.to_string_lossy(), &svg_path.to_string_lossy(), "svg", timeout,
+        )
+        .and_then(|_| Ok(std::fs::read_to_string(&svg_path)?));
+        let _ = std::fs::remove_file(&dot_path);
+        let _ = std::fs::remove_file(&svg_path);
+        rendered
     }
 }
 
@@ -204,33 +421,60 @@ impl CausalGraph {
 pub struct GraphvizExporter;
 
 impl GraphvizExporter {
+    /// How long `dot` may run before it is killed
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+
     /// Generate SVG from DOT (requires graphviz installed)
     pub fn dot_to_svg(dot_path: &str, svg_path: &str) -> Result<()> {
-        use std::process::Command;
-        
-        let output = Command::new("dot")
-            .args(["-Tsvg", dot_path, "-o", svg_path])
-            .output()?;
-        
-        if !output.status.success() {
-            anyhow::bail!("Graphviz failed: {}", String::from_utf8_lossy(&output.stderr));
-        }
-        
-        Ok(())
+        Self::render(dot_path, svg_path, "svg", Self::DEFAULT_TIMEOUT)
     }
 
     /// Generate PNG from DOT
     pub fn dot_to_png(dot_path: &str, png_path: &str) -> Result<()> {
-        use std::process::Command;
-        
-        let output = Command::new("dot")
-            .args(["-Tpng", dot_path, "-o", png_path])
-            .output()?;
-        
-        if !output.status.success() {
-            anyhow::bail!("Graphviz failed: {}", String::from_utf8_lossy(&output.stderr));
+        Self::render(dot_path, png_path, "png", Self::DEFAULT_TIMEOUT)
+    }
+
+    /// Run `dot -T<format>`, killing it if it hasn't finished within `timeout`
+    pub fn render(dot_path: &str, out_path: &str, format: &str, timeout: Duration) -> Result<()> {
+        use std::io::Read;
+        use std::process::{Command, Stdio};
+
+        let mut child = Command::new("dot")
+            .arg(format!("-T{}", format))
+            .args([dot_path, "-o", out_path])
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| DeepCausalityError::Export(format!("Failed to run Graphviz: {}", e)))?;
+
+        // Drain stderr on a thread so a chatty `dot` can't stall on a full pipe
+        let mut stderr = child.stderr.take().expect("stderr is piped");
+        let reader = std::thread::spawn(move || {
+            let mut buf = String::new();
+            let _ = stderr.read_to_string(&mut buf);
+            buf
+        });
+
+        let start = Instant::now();
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break status;
+            }
+            if start.elapsed() >= timeout {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(DeepCausalityError::Export(format!(
+                    "Graphviz timed out after {:?} rendering {}", timeout, dot_path
+                )));
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        };
+
+        let stderr = reader.join().unwrap_or_default();
+        if !status.success() {
+            return Err(DeepCausalityError::Export(format!("Graphviz failed: {}", stderr)));
         }
-        
+
         Ok(())
     }
 }
@@ -256,6 +500,70 @@ mod tests {
         assert!(dot.contains("ICULOS"));
     }
 
+    #[test]
+    fn test_clusters_emit_subgraphs() {
+        let features = vec![
+            ("Temp".to_string(), 0.9),
+            ("Temp_core".to_string(), 0.85),
+            ("HR".to_string(), 0.7),
+            ("MAP".to_string(), 0.6),
+        ];
+        let mut graph = CausalGraph::from_mrmr_results(&features, "SepsisLabel");
+        graph.add_cluster("Temperature", &["Temp".to_string(), "Temp_core".to_string()]);
+        graph.add_cluster("Hemodynamics", &["HR".to_string(), "MAP".to_string(), "Lactate".to_string()]);
+        assert_eq!(graph.clusters[1].node_ids, vec!["hr", "map"]);
+
+        let dot = graph.to_dot();
+        assert!(dot.contains("subgraph cluster_0 {\n    label=\"Temperature\";"));
+        assert!(dot.contains("subgraph cluster_1 {\n    label=\"Hemodynamics\";"));
+        // Each clustered node is declared once, inside its box
+        assert_eq!(dot.matches("temp_core [").count(), 1);
+        assert!(dot.contains("    temp_core [label="));
+    }
+
+    #[test]
+    fn test_graph_from_surd() {
+        use crate::causality::FeatureSurdTerms;
+
+        let feature = |redundant, unique, synergistic| FeatureSurdTerms { redundant, unique, synergistic };
+        let terms = crate::causality::SurdAnalysisResult {
+            redundant_info: 0.2,
+            unique_info: 0.3,
+            synergistic_info: 0.5,
+            total_info: 1.0,
+            estimation_reliability: None,
+            skipped: Vec::new(),
+            per_feature: [
+                ("Lactate".to_string(), feature(0.0, 0.3, 0.0)),
+                ("HR".to_string(), feature(0.1, 0.0, 0.25)),
+                ("MAP".to_string(), feature(0.1, 0.0, 0.25)),
+            ].into_iter().collect(),
+        };
+        let result = SurdDualResult {
+            sepsis_result: terms.clone(),
+            non_sepsis_result: terms,
+            disjoint_drivers: vec!["Lactate".to_string()],
+            shared_drivers: vec!["HR".to_string(), "MAP".to_string()],
+            sepsis_specific_score: 0.0,
+        };
+
+        let graph = CausalGraph::from_surd_result(&result, "SepsisLabel");
+        assert_eq!(graph.nodes.len(), 7); // target + 3 mechanisms + 3 features
+        assert_eq!(graph.edges.len(), 8); // 3 mechanisms + one per non-zero feature term
+
+        let synergy = graph.edges.iter().find(|e| e.from == "synergistic").unwrap();
+        assert_eq!(synergy.edge_type, EdgeType::Synergistic);
+        assert!((synergy.weight - 0.5).abs() < 1e-12);
+
+        // Features attach by their own SURD terms, not by cohort membership
+        let lactate: Vec<_> = graph.edges.iter().filter(|e| e.from == "lactate").collect();
+        assert_eq!(lactate.len(), 1);
+        assert_eq!((lactate[0].to.as_str(), lactate[0].edge_type), ("unique", EdgeType::Causal));
+        assert!((lactate[0].weight - 0.3).abs() < 1e-12);
+        let hr_targets: Vec<_> = graph.edges.iter().filter(|e| e.from == "hr").map(|e| e.to.as_str()).collect();
+        assert_eq!(hr_targets, vec!["redundant", "synergistic"]);
+    }
+
     #[test]
     fn test_dot_format() {
         let mut graph = CausalGraph::new("Test Graph");
@@ -266,4 +574,59 @@ mod tests {
         let dot = graph.to_dot();
         assert!(dot.contains("a -> b"));
     }
+
+    #[test]
+    fn test_dot_labels_are_escaped() {
+        let mut graph = CausalGraph::new("Sepsis \"v2\"");
+        let lactate = "C:\\labs \"Lactate\"".to_string();
+        graph.add_node("a", lactate.clone(), NodeType::Feature);
+        graph.add_cluster("Labs \"panel\"", &[lactate]);
+
+        let dot = graph.to_dot();
+        assert!(dot.contains("label=\"Sepsis \\\"v2\\\"\";"));
+        assert!(dot.contains("label=\"C:\\\\labs \\\"Lactate\\\"\""));
+        assert!(dot.contains("label=\"Labs \\\"panel\\\"\";"));
+    }
+
+    #[test]
+    fn test_themed_dot() {
+        let mut graph = CausalGraph::new("Theme Graph");
+        graph.add_node("a", "Feature A", NodeType::Feature);
+
+        assert!(graph.to_dot().contains("bgcolor=\"#1a1a2e\""));
+        assert!(graph.to_dot_themed(GraphTheme::Light).contains("bgcolor=\"#ffffff\""));
+        assert!(graph.to_dot_themed(GraphTheme::Print).contains("fontcolor=\"#000000\""));
+    }
+
+    #[test]
+    fn test_graph_enums_parse() {
+        assert_eq!("mechanism".parse::<NodeType>(), Ok(NodeType::Mechanism));
+        assert_eq!("synergistic".parse::<EdgeType>(), Ok(EdgeType::Synergistic));
+        assert_eq!("print".parse::<GraphTheme>(), Ok(GraphTheme::Print));
+        assert!("Feature".parse::<NodeType>().unwrap_err().contains("expected feature"));
+    }
+
+    #[test]
+    fn test_render_completes_within_timeout() -> Result<()> {
+        // Graphviz is an optional system dependency
+        if std::process::Command::new("dot").arg("-V").output().is_err() {
+            return Ok(());
+        }
+
+        let mut graph = CausalGraph::new("Trivial");
+        graph.add_node("a", "A", NodeType::Feature);
+        let dir = std::env::temp_dir();
+        let dot_path = dir.join(format!("render_timeout_test_{}.dot", std::process::id()));
+        let svg_path = dir.join(format!("render_timeout_test_{}.svg", std::process::id()));
+        graph.write_dot(dot_path.to_str().unwrap())?;
+
+        GraphvizExporter::render(dot_path.to_str().unwrap(), svg_path.to_str().unwrap(), "svg", Duration::from_secs(10))?;
+        assert!(svg_path.exists());
+
+        std::fs::remove_file(dot_path)?;
+        std::fs::remove_file(svg_path)?;
+
+        assert!(graph.to_svg(GraphTheme::Light, Duration::from_secs(10))?.contains("<svg"));
+        Ok(())
+    }
 }
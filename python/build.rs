@@ -0,0 +1,75 @@
+//! Bake build metadata into the extension for `build_info()`
+//!
+//! Everything is read at compile time, so the values describe the binary that is
+//! actually loaded rather than whatever checkout happens to be on disk later.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let root = Path::new(&manifest_dir).join("..");
+    let lock_path = root.join("Cargo.lock");
+    println!("cargo:rerun-if-changed={}", lock_path.display());
+    let git_dir = root.join(".git");
+    println!("cargo:rerun-if-changed={}", git_dir.join("HEAD").display());
+    // HEAD only changes on checkout; a commit moves the branch it points to
+    if let Some(head_ref) = fs::read_to_string(git_dir.join("HEAD")).ok()
+        .and_then(|head| head.trim().strip_prefix("ref: ").map(str::to_string))
+    {
+        let ref_path = git_dir.join(&head_ref);
+        // A packed ref has no file of its own until the branch moves again
+        let watched = if ref_path.exists() { ref_path } else { git_dir.join("packed-refs") };
+        println!("cargo:rerun-if-changed={}", watched.display());
+    }
+
+    // Builds from an sdist have no git checkout
+    let git_hash = Command::new("git")
+        .args(["rev-parse", "--short=12", "HEAD"])
+        .current_dir(&root)
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map(|hash| hash.trim().to_string())
+        .filter(|hash| !hash.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+    println!("cargo:rustc-env=DEEP_CAUSALITY_PY_GIT_HASH={}", git_hash);
+
+    let mut features: Vec<String> = env::vars()
+        .filter_map(|(key, _)| key.strip_prefix("CARGO_FEATURE_").map(|f| f.to_lowercase().replace('_', "-")))
+        .collect();
+    features.sort();
+    println!("cargo:rustc-env=DEEP_CAUSALITY_PY_FEATURES={}", features.join(","));
+    println!("cargo:rustc-env=DEEP_CAUSALITY_PY_PROFILE={}", env::var("PROFILE").unwrap_or_default());
+
+    let lock = fs::read_to_string(&lock_path).unwrap_or_default();
+    println!("cargo:rustc-env=DEEP_CAUSALITY_PY_LINKED={}", linked_crates(&lock).join(";"));
+}
+
+/// "name=version@rev" for every external deep_causality crate in the lockfile; rev is
+/// the short git commit, empty for registry crates
+fn linked_crates(lock: &str) -> Vec<String> {
+    let mut crates = Vec::new();
+    for package in lock.split("[[package]]").skip(1) {
+        let field = |key: &str| {
+            package.lines()
+                .find_map(|line| line.strip_prefix(key)?.trim().strip_prefix("= ")?.strip_prefix('"')?.strip_suffix('"'))
+        };
+        let Some(name) = field("name").filter(|n| n.starts_with("deep_causality")) else {
+            continue;
+        };
+        // Workspace members have no source
+        let Some(source) = field("source") else {
+            continue;
+        };
+        let version = field("version").unwrap_or("unknown");
+        let rev = source.split_once('#')
+            .map(|(_, rev)| &rev[..rev.len().min(12)])
+            .unwrap_or("");
+        crates.push(format!("{}={}@{}", name, version, rev));
+    }
+    crates
+}
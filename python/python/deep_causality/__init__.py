@@ -8,51 +8,199 @@ Example:
     >>> import polars as pl
     >>> 
     >>> df = pl.read_parquet("data.parquet")
-    >>> features = deep_causality.run_mrmr(df, target="SepsisLabel", max_features=10)
+    >>> features = deep_causality.run_mrmr_polars(df, target="SepsisLabel", max_features=10)
     >>> for f in features:
     ...     print(f"{f.name}: {f.score:.4f}")
 """
 
+import warnings
+
 from deep_causality._core import (
+    CandidateScore,
+    CausalDataset,
+    CausalGraph,
+    CausalPipeline,
+    ColumnTypeError,
+    CounterfactualExplanation,
+    DeepCausalityError,
+    EthosGuard,
     FeatureRanking,
+    MissingColumnError,
+    MrmrError,
+    MrmrFullResult,
+    MrmrSelector,
+    PatientData,
+    PipelineResult,
+    SelectionStep,
+    StreamingDataset,
+    SurdError,
+    SurdDualResult,
     SurdResult,
+    TensorShapeError,
+    TransferEntropyScore,
+    build_info,
+    encode_columns,
+    load_dataset,
+    pairwise_information_matrix,
+    profile_data,
+    run_mrmr_from_dict as _run_mrmr_from_dict,
     run_mrmr,
-    run_mrmr_from_dict,
+    run_mrmr_conditional,
+    run_mrmr_full,
+    run_mrmr_numpy,
+    run_mrmr_polars,
+    run_surd,
+    run_surd_dual,
+    run_transfer_entropy,
     version,
 )
 
 __version__ = version()
 __all__ = [
+    "CandidateScore",
+    "CausalDataset",
+    "CausalGraph",
+    "CausalPipeline",
+    "ColumnTypeError",
+    "CounterfactualExplanation",
+    "DeepCausalityError",
+    "EthosGuard",
     "FeatureRanking",
-    "SurdResult", 
+    "MissingColumnError",
+    "MrmrError",
+    "MrmrFullResult",
+    "MrmrResult",
+    "MrmrSelector",
+    "PatientData",
+    "PipelineResult",
+    "SelectionStep",
+    "StreamingDataset",
+    "SurdError",
+    "SurdDualResult",
+    "SurdResult",
+    "TensorShapeError",
+    "TransferEntropyScore",
+    "build_info",
+    "encode_columns",
+    "load_dataset",
+    "pairwise_information_matrix",
+    "profile_data",
     "run_mrmr",
+    "run_mrmr_conditional",
     "run_mrmr_from_dict",
+    "run_mrmr_full",
+    "run_mrmr_numpy",
+    "run_mrmr_pandas",
     "run_mrmr_polars",
+    "run_surd",
+    "run_surd_dual",
+    "run_transfer_entropy",
     "version",
 ]
 
 
-def run_mrmr_polars(df, target: str, max_features: int = 10):
+class MrmrResult(list):
+    """List of FeatureRanking objects that also records which input columns were dropped
+    and, for a label-encoded target, its ``{class: code}`` table."""
+
+    def __init__(self, features, dropped_columns, target_classes=None):
+        super().__init__(features)
+        self.dropped_columns = dropped_columns
+        self.target_classes = target_classes
+
+
+def _encode_targets(columns, targets):
+    """Label-encode non-numeric target columns; returns ({target: codes}, {target: {class: code}}).
+
+    Relevance is a correlation with the codes, which only ignores how the classes were
+    coded when there are two of them, so targets with more classes are rejected.
+    """
+    encoded, classes = encode_columns({target: columns[target] for target in targets})
+    for target, table in classes.items():
+        if len(table) > 2:
+            raise ValueError(
+                f"Target '{target}' has {len(table)} classes ({', '.join(sorted(table))}); mRMR "
+                "ranks against a binary target only, so compare one class with the rest"
+            )
+    return {target: encoded[target] for target in classes}, classes
+
+
+def run_mrmr_from_dict(df_dict, target_column, max_features: int = 10, **options):
+    """
+    Run mRMR on a dict of columns. A target column holding strings or other
+    non-numeric labels is label-encoded first (see ``encode_columns``); feature
+    columns must be numeric.
+
+    Args:
+        df_dict: Dictionary mapping column names to lists of values
+        target_column: Name of the target column, or a list of them as in ``run_mrmr``
+        max_features: Maximum number of features to select (default: 10)
+        **options: Keyword options accepted by ``run_mrmr``
+
+    Returns:
+        MrmrResult: FeatureRanking objects, plus ``.target_classes`` when the target
+        was encoded; for a list of targets, a dict mapping each target to its MrmrResult
+    """
+    targets = [target_column] if isinstance(target_column, str) else list(target_column)
+    missing = [target for target in targets if target not in df_dict]
+    if missing:
+        raise MissingColumnError(f"Target column '{missing[0]}' not found")
+
+    codes, classes = _encode_targets(df_dict, targets)
+    result = _run_mrmr_from_dict({**df_dict, **codes}, target_column, max_features, **options)
+    if isinstance(target_column, str):
+        return MrmrResult(result, [], classes.get(target_column))
+    return {target: MrmrResult(features, [], classes.get(target)) for target, features in result.items()}
+
+
+def run_mrmr_pandas(df, target_column: str, max_features: int = 10, **options):
     """
-    Run mRMR feature selection on a Polars DataFrame.
-    
+    Run mRMR feature selection on a pandas DataFrame.
+
+    Numeric and boolean columns (as 0/1) are handed to the engine as one float64
+    block through the NumPy buffer, without building Python lists. A string or categorical target is
+    label-encoded first (see ``encode_columns``). Other non-numeric columns are
+    dropped with a warning and listed in the result's ``dropped_columns``.
+
     Args:
-        df: Polars DataFrame with numeric columns
-        target: Name of the target column
+        df: pandas DataFrame
+        target_column: Name of the target column
         max_features: Maximum number of features to select (default: 10)
-    
+        **options: Keyword options accepted by ``run_mrmr``; ``sample_weights`` may be a
+            Series or array with one weight per row
+
     Returns:
-        List of FeatureRanking objects with .name and .score attributes
-    
-    Example:
-        >>> import polars as pl
-        >>> import deep_causality
-        >>> 
-        >>> df = pl.read_parquet("icu_data.parquet")
-        >>> features = deep_causality.run_mrmr_polars(df, target="SepsisLabel")
-        >>> print(features[0])
-        FeatureRanking(name='ICULOS', score=1.0000)
+        MrmrResult: FeatureRanking objects, plus ``.dropped_columns`` and, when the
+        target was encoded, ``.target_classes``
     """
-    # Convert Polars DataFrame to dict of lists
-    df_dict = {col: df[col].to_list() for col in df.columns}
-    return run_mrmr_from_dict(df_dict, target, max_features)
+    import numpy as np
+
+    if target_column not in df.columns:
+        raise MissingColumnError(f"Target column '{target_column}' not found")
+
+    numeric = df.select_dtypes(include=["number", "bool", "boolean"])
+    # Flags become 0/1; the nullable Float64 dtype keeps missing flags missing
+    flags = numeric.select_dtypes(include=["bool", "boolean"]).columns
+    if len(flags):
+        numeric = numeric.astype({col: "Float64" for col in flags})
+    target_classes = None
+    if target_column not in numeric.columns:
+        # Missing labels (NaN, None, pd.NA) stay missing rather than becoming a class
+        target = df[target_column].astype(object)
+        labels = target.where(target.notna(), None).tolist()
+        codes, classes = _encode_targets({target_column: labels}, [target_column])
+        numeric = numeric.copy()
+        numeric[target_column] = np.array(codes.get(target_column, labels), dtype=np.float64)
+        target_classes = classes.get(target_column)
+    dropped = [str(col) for col in df.columns if col not in numeric.columns]
+    if dropped:
+        warnings.warn(f"Dropping non-numeric columns: {', '.join(dropped)}", stacklevel=2)
+
+    # Nullable pandas dtypes carry pd.NA, which becomes NaN (missing) here
+    array = numeric.to_numpy(dtype=np.float64, na_value=np.nan)
+    if options.get("sample_weights") is not None:
+        # Accept a Series or array aligned with the rows
+        options["sample_weights"] = np.asarray(options["sample_weights"], dtype=np.float64).tolist()
+    features = run_mrmr_numpy(array, [str(col) for col in numeric.columns],
+                              target_column, max_features, **options)
+    return MrmrResult(features, dropped, target_classes)
@@ -0,0 +1,310 @@
+//! A dataset converted once and kept resident in Rust
+//!
+//! The module-level functions convert their input on every call; notebooks that
+//! run several analyses over the same wide dataset can load it into a
+//! `CausalDataset` instead and skip the repeated transfer across the FFI boundary,
+//! or read it from disk with `load_dataset` without it ever entering Python.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use numpy::PyReadonlyArray2;
+use pyo3_polars::PyDataFrame;
+use polars::prelude::*;
+use deep_causality_tensor::CausalTensor;
+use icu_sepsis_backend::data::profile::profile_tensor;
+use icu_sepsis_backend::data::DataLoader;
+use icu_sepsis_backend::causality::CorrelationMoments;
+use icu_sepsis_backend::utils::tensor_adapter::TensorAdapter;
+
+use crate::{
+    backend_err, mrmr_on_tensor, numpy_to_tensor, parse_discretizer, profiles_to_dict, py_data_to_tensor, surd_on_tensor,
+    DeepCausalityError, FeatureRanking, MrmrFullResult, SurdResult, TensorShapeError,
+};
+use crate::streaming::mrmr_on_moments;
+
+/// Column-major tensor and its column names, validated once at load time
+#[pyclass]
+pub struct CausalDataset {
+    tensor: CausalTensor<Option<f64>>,
+    column_names: Vec<String>,
+    /// Correlation moments of every row, built by the first `update_mrmr` and grown by later ones
+    moments: Option<CorrelationMoments>,
+    /// Row chunks added by `update_mrmr` and not yet merged into `tensor`; merged by the
+    /// first method that reads every row
+    pending: Vec<CausalTensor<Option<f64>>>,
+}
+
+impl CausalDataset {
+    fn new_checked(tensor: CausalTensor<Option<f64>>, column_names: Vec<String>) -> PyResult<Self> {
+        let n_cols = tensor.shape()[1];
+        if column_names.len() != n_cols {
+            return Err(TensorShapeError::new_err(format!(
+                "Got {} column names but data has {} columns",
+                column_names.len(), n_cols
+            )));
+        }
+        Ok(Self { tensor, column_names, moments: None, pending: Vec::new() })
+    }
+
+    /// Fold the pending `update_mrmr` chunks into the resident tensor
+    fn merge_pending(&mut self, py: Python) -> PyResult<()> {
+        if self.pending.is_empty() {
+            return Ok(());
+        }
+        self.tensor = py.allow_threads(|| TensorAdapter::append_rows(&self.tensor, &self.pending))
+            .map_err(backend_err)?;
+        self.pending.clear();
+        Ok(())
+    }
+
+    /// Present values of one column
+    fn column(&self, idx: usize) -> impl Iterator<Item = f64> + '_ {
+        let height = self.tensor.shape()[0];
+        self.tensor.as_slice()[idx * height..(idx + 1) * height].iter().flatten().copied()
+    }
+}
+
+#[pymethods]
+impl CausalDataset {
+    /// Load row-major 2D list data; None or NaN marks a missing value
+    #[new]
+    fn new(py: Python, data: Vec<Vec<Option<f64>>>, column_names: Vec<String>) -> PyResult<Self> {
+        py.allow_threads(|| {
+            let (tensor, _, _) = py_data_to_tensor(data)?;
+            Self::new_checked(tensor, column_names)
+        })
+    }
+
+    /// Load a 2D float64 NumPy array (rows x columns)
+    #[staticmethod]
+    fn from_numpy(array: PyReadonlyArray2<f64>, column_names: Vec<String>) -> PyResult<Self> {
+        Self::new_checked(numpy_to_tensor(&array)?, column_names)
+    }
+
+    /// Load a Polars DataFrame through the Arrow C data interface
+    #[staticmethod]
+    fn from_polars(py: Python, df: PyDataFrame) -> PyResult<Self> {
+        let df: DataFrame = df.into();
+        if df.height() == 0 {
+            return Err(TensorShapeError::new_err("Empty data"));
+        }
+        py.allow_threads(|| {
+            let (tensor, column_names) = TensorAdapter::df_to_tensor(&df)
+                .map_err(|e| DeepCausalityError::new_err(e.to_string()))?;
+            Self::new_checked(tensor, column_names)
+        })
+    }
+
+    #[getter]
+    fn column_names(&self) -> Vec<String> {
+        self.column_names.clone()
+    }
+
+    /// (rows, columns)
+    #[getter]
+    fn shape(&self) -> (usize, usize) {
+        let pending: usize = self.pending.iter().map(|chunk| chunk.shape()[0]).sum();
+        (self.tensor.shape()[0] + pending, self.tensor.shape()[1])
+    }
+
+    /// A new dataset of `n` randomly drawn rows; the same `random_seed` always draws
+    /// the same rows. Without replacement the rows keep their original order.
+    #[pyo3(signature = (n, with_replacement=false, random_seed=None))]
+    fn sample(&mut self, py: Python, n: usize, with_replacement: bool, random_seed: Option<u64>) -> PyResult<Self> {
+        self.merge_pending(py)?;
+        py.allow_threads(|| {
+            let tensor = TensorAdapter::sample_rows(&self.tensor, n, with_replacement, random_seed)
+                .map_err(backend_err)?;
+            Ok(Self { tensor, column_names: self.column_names.clone(), moments: None, pending: Vec::new() })
+        })
+    }
+
+    /// Run mRMR on the resident data; takes the keyword options of `run_mrmr`
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (target_column, max_features=10, min_score=None, include_columns=None, exclude_columns=None, normalize_scores=false, n_bins=10, discretization=None, progress_callback=None, n_threads=None))]
+    fn mrmr(
+        &mut self,
+        py: Python,
+        target_column: String,
+        max_features: usize,
+        min_score: Option<f64>,
+        include_columns: Option<Vec<String>>,
+        exclude_columns: Option<Vec<String>>,
+        normalize_scores: bool,
+        n_bins: usize,
+        discretization: Option<String>,
+        progress_callback: Option<PyObject>,
+        n_threads: Option<usize>,
+    ) -> PyResult<Vec<FeatureRanking>> {
+        self.merge_pending(py)?;
+        py.allow_threads(|| mrmr_on_tensor(
+            self.tensor.clone(), self.column_names.clone(), target_column, max_features, min_score,
+            include_columns, exclude_columns, normalize_scores, n_bins, discretization, progress_callback, n_threads,
+            false,
+        ))
+        .map(|result| result.features)
+    }
+
+    /// Like `mrmr`, also returning every candidate's score at each step, as `run_mrmr_full` does
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (target_column, max_features=10, min_score=None, include_columns=None, exclude_columns=None, normalize_scores=false, n_bins=10, discretization=None, progress_callback=None, n_threads=None))]
+    fn mrmr_full(
+        &mut self,
+        py: Python,
+        target_column: String,
+        max_features: usize,
+        min_score: Option<f64>,
+        include_columns: Option<Vec<String>>,
+        exclude_columns: Option<Vec<String>>,
+        normalize_scores: bool,
+        n_bins: usize,
+        discretization: Option<String>,
+        progress_callback: Option<PyObject>,
+        n_threads: Option<usize>,
+    ) -> PyResult<MrmrFullResult> {
+        self.merge_pending(py)?;
+        py.allow_threads(|| mrmr_on_tensor(
+            self.tensor.clone(), self.column_names.clone(), target_column, max_features, min_score,
+            include_columns, exclude_columns, normalize_scores, n_bins, discretization, progress_callback, n_threads,
+            true,
+        ))
+    }
+
+    /// Append `new_rows` (row-major 2D list or float64 NumPy array, in `column_names`
+    /// order) and rerun mRMR over every row. The first call scans the resident data
+    /// once; later calls only fold in the new rows, which are kept as separate chunks
+    /// until a method reading every row merges them, so refreshes cost the size of the
+    /// update rather than of the history. Takes the keyword options of `run_mrmr` except
+    /// discretization, which needs all rows at once.
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (new_rows, target_column, max_features=10, min_score=None, include_columns=None, exclude_columns=None, normalize_scores=false, progress_callback=None, n_threads=None))]
+    fn update_mrmr(
+        &mut self,
+        py: Python,
+        new_rows: &PyAny,
+        target_column: String,
+        max_features: usize,
+        min_score: Option<f64>,
+        include_columns: Option<Vec<String>>,
+        exclude_columns: Option<Vec<String>>,
+        normalize_scores: bool,
+        progress_callback: Option<PyObject>,
+        n_threads: Option<usize>,
+    ) -> PyResult<Vec<FeatureRanking>> {
+        let rows = match new_rows.extract::<PyReadonlyArray2<f64>>() {
+            Ok(array) => numpy_to_tensor(&array)?,
+            Err(_) => {
+                let data: Vec<Vec<Option<f64>>> = new_rows.extract()?;
+                if data.is_empty() {
+                    TensorAdapter::column_major_tensor(Vec::new(), 0, self.column_names.len()).map_err(backend_err)?
+                } else {
+                    py.allow_threads(|| py_data_to_tensor(data))?.0
+                }
+            }
+        };
+        if rows.shape()[1] != self.column_names.len() {
+            return Err(TensorShapeError::new_err(format!(
+                "New rows have {} columns but the dataset has {}", rows.shape()[1], self.column_names.len()
+            )));
+        }
+
+        py.allow_threads(|| -> PyResult<()> {
+            if self.moments.is_none() {
+                let mut moments = CorrelationMoments::new(self.column_names.len());
+                moments.update(&self.tensor).map_err(backend_err)?;
+                for chunk in &self.pending {
+                    moments.update(chunk).map_err(backend_err)?;
+                }
+                self.moments = Some(moments);
+            }
+            self.moments.as_mut().expect("moments are built above").update(&rows).map_err(backend_err)
+        })?;
+        self.pending.push(rows);
+
+        let moments = self.moments.as_ref().expect("moments are built above");
+        mrmr_on_moments(
+            py, moments, &self.column_names, target_column, max_features, min_score,
+            include_columns, exclude_columns, normalize_scores, progress_callback, n_threads,
+        )
+        .map(|result| result.features)
+    }
+
+    /// SURD decomposition of `target_column` over every other column
+    #[pyo3(signature = (target_column, n_bins=10, discretization=None))]
+    fn surd(&mut self, py: Python, target_column: String, n_bins: usize, discretization: Option<String>) -> PyResult<SurdResult> {
+        let discretizer = parse_discretizer(n_bins, discretization.as_deref())?;
+        self.merge_pending(py)?;
+        py.allow_threads(|| surd_on_tensor(&self.tensor, &self.column_names, &target_column, discretizer))
+    }
+
+    /// Column name -> {"count", "missing", "mean", "std", "min", "max"}; statistics of
+    /// an all-missing column are NaN
+    fn describe(&mut self, py: Python) -> PyResult<PyObject> {
+        self.merge_pending(py)?;
+        let height = self.tensor.shape()[0];
+        let summary = PyDict::new(py);
+        for (idx, name) in self.column_names.iter().enumerate() {
+            let (count, sum) = self.column(idx).fold((0usize, 0.0), |(n, sum), v| (n + 1, sum + v));
+            let mean = if count > 0 { sum / count as f64 } else { f64::NAN };
+            // Sample standard deviation, matching pandas and Polars
+            let std = if count > 1 {
+                (self.column(idx).map(|v| (v - mean).powi(2)).sum::<f64>() / (count - 1) as f64).sqrt()
+            } else {
+                f64::NAN
+            };
+            let min = self.column(idx).reduce(f64::min).unwrap_or(f64::NAN);
+            let max = self.column(idx).reduce(f64::max).unwrap_or(f64::NAN);
+
+            let stats = PyDict::new(py);
+            stats.set_item("count", count)?;
+            stats.set_item("missing", height - count)?;
+            stats.set_item("mean", mean)?;
+            stats.set_item("std", std)?;
+            stats.set_item("min", min)?;
+            stats.set_item("max", max)?;
+            summary.set_item(name, stats)?;
+        }
+        Ok(summary.into())
+    }
+
+    /// Data-quality report in the format of `profile_data`
+    #[pyo3(signature = (target_column=None))]
+    fn profile(&mut self, py: Python, target_column: Option<String>) -> PyResult<PyObject> {
+        self.merge_pending(py)?;
+        let profiles = py.allow_threads(|| profile_tensor(&self.tensor, &self.column_names, target_column.as_deref()))
+            .map_err(backend_err)?;
+        profiles_to_dict(py, profiles)
+    }
+
+    fn __repr__(&self) -> String {
+        let (rows, cols) = self.shape();
+        format!("CausalDataset(rows={}, columns={})", rows, cols)
+    }
+}
+
+/// Read a Parquet, Feather/Arrow IPC or CSV file straight into a `CausalDataset`
+///
+/// The file is parsed by the extension's own Polars, so its contents never pass
+/// through the Python heap. `columns` keeps only those columns, e.g. to drop
+/// identifiers or text columns that can't become tensor values.
+#[pyfunction]
+#[pyo3(signature = (path, columns=None))]
+fn load_dataset(py: Python, path: String, columns: Option<Vec<String>>) -> PyResult<CausalDataset> {
+    py.allow_threads(|| {
+        let mut df = DataLoader::load_file(&path).map_err(backend_err)?;
+        if let Some(columns) = columns {
+            df = df.select(columns).map_err(|e| backend_err(e.into()))?;
+        }
+        if df.height() == 0 {
+            return Err(TensorShapeError::new_err(format!("{} contains no rows", path)));
+        }
+        let (tensor, column_names) = TensorAdapter::df_to_tensor(&df).map_err(backend_err)?;
+        CausalDataset::new_checked(tensor, column_names)
+    })
+}
+
+pub fn register(m: &PyModule) -> PyResult<()> {
+    m.add_class::<CausalDataset>()?;
+    m.add_function(wrap_pyfunction!(load_dataset, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,235 @@
+//! Python wrappers for the Ethos compliance guardrails
+//!
+//! Services can run the same deontic checks as the backend before acting on a
+//! model output.
+
+use std::collections::HashMap;
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use icu_sepsis_backend::ethos::{
+    CounterfactualExplanation, CriticalFeatureMissingRule, EthosGuard, MaxUncertaintyThreshold,
+    PatientData, RequireCriticalVitals,
+};
+use serde::{Serialize, Deserialize};
+
+use crate::serialization::impl_serialization;
+
+/// A critical feature as (name, weight, tolerance) or (name, weight)
+#[derive(FromPyObject)]
+enum WeightedFeature {
+    WithTolerance(String, f64, f64),
+    Weighted(String, f64),
+}
+
+/// Vitals, lab values and metadata for one patient; None marks a missing value
+#[pyclass(name = "PatientData")]
+#[derive(Clone)]
+pub struct PyPatientData {
+    inner: PatientData,
+}
+
+#[pymethods]
+impl PyPatientData {
+    #[new]
+    #[pyo3(signature = (vitals=None, labs=None))]
+    fn new(
+        vitals: Option<HashMap<String, Option<f64>>>,
+        labs: Option<HashMap<String, Option<f64>>>,
+    ) -> Self {
+        let mut inner = PatientData::new();
+        for (name, value) in vitals.unwrap_or_default() {
+            inner.set_vital(name, value);
+        }
+        for (name, value) in labs.unwrap_or_default() {
+            inner.set_lab(name, value);
+        }
+        Self { inner }
+    }
+
+    fn set_vital(&mut self, name: String, value: Option<f64>) {
+        self.inner.set_vital(name, value);
+    }
+
+    fn set_lab(&mut self, name: String, value: Option<f64>) {
+        self.inner.set_lab(name, value);
+    }
+
+    fn set_metadata(&mut self, key: String, value: String) {
+        self.inner.metadata.insert(key, value);
+    }
+
+    fn get_vital(&self, name: &str) -> Option<f64> {
+        self.inner.get_vital(name)
+    }
+
+    fn get_lab(&self, name: &str) -> Option<f64> {
+        self.inner.get_lab(name)
+    }
+
+    fn is_vital_missing(&self, name: &str) -> bool {
+        self.inner.is_vital_missing(name)
+    }
+
+    fn is_lab_missing(&self, name: &str) -> bool {
+        self.inner.is_lab_missing(name)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PatientData(vitals={}, labs={})",
+            self.inner.vitals.len(), self.inner.lab_values.len()
+        )
+    }
+}
+
+/// Why an action was blocked and what would have to change for it to proceed
+#[pyclass(name = "CounterfactualExplanation", module = "deep_causality._core")]
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PyCounterfactualExplanation {
+    inner: CounterfactualExplanation,
+}
+
+#[pymethods]
+impl PyCounterfactualExplanation {
+    #[getter]
+    fn blocked_action(&self) -> &str {
+        &self.inner.blocked_action
+    }
+
+    #[getter]
+    fn rule_violated(&self) -> &str {
+        &self.inner.rule_violated
+    }
+
+    #[getter]
+    fn rule_id(&self) -> &str {
+        &self.inner.rule_id
+    }
+
+    #[getter]
+    fn counterfactual(&self) -> &str {
+        &self.inner.counterfactual
+    }
+
+    #[getter]
+    fn severity(&self) -> u8 {
+        self.inner.severity
+    }
+
+    #[getter]
+    fn context(&self) -> HashMap<String, String> {
+        self.inner.context.clone()
+    }
+
+    fn to_dict(&self, py: Python) -> PyResult<PyObject> {
+        explanation_dict(py, &self.inner)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "CounterfactualExplanation(rule_id='{}', severity={}, rule_violated='{}')",
+            self.inner.rule_id, self.inner.severity, self.inner.rule_violated
+        )
+    }
+}
+
+impl_serialization!(PyCounterfactualExplanation);
+
+fn explanation_dict(py: Python, explanation: &CounterfactualExplanation) -> PyResult<PyObject> {
+    let dict = PyDict::new(py);
+    dict.set_item("blocked_action", &explanation.blocked_action)?;
+    dict.set_item("rule_violated", &explanation.rule_violated)?;
+    dict.set_item("rule_id", &explanation.rule_id)?;
+    dict.set_item("counterfactual", &explanation.counterfactual)?;
+    dict.set_item("severity", explanation.severity)?;
+    dict.set_item("context", explanation.context.clone())?;
+    Ok(dict.into())
+}
+
+/// Guard that checks patient data against a set of Ethos rules
+#[pyclass(name = "EthosGuard")]
+pub struct PyEthosGuard {
+    inner: EthosGuard,
+}
+
+#[pymethods]
+impl PyEthosGuard {
+    /// An empty guard; add rules with the `add_*` methods
+    #[new]
+    fn new() -> Self {
+        Self { inner: EthosGuard::new() }
+    }
+
+    /// The backend's default clinical rules (critical vitals, missing-data threshold)
+    #[staticmethod]
+    fn clinical_default() -> Self {
+        Self { inner: EthosGuard::clinical_default() }
+    }
+
+    /// Block when any of these vitals is missing
+    fn add_required_vitals(&mut self, vitals: Vec<String>) {
+        let vitals = vitals.iter().map(String::as_str).collect();
+        self.inner.add_rule(Box::new(RequireCriticalVitals::new(vitals)));
+    }
+
+    /// Block when more than `threshold` (0..1) of the data is missing
+    fn add_max_uncertainty(&mut self, threshold: f64) {
+        self.inner.add_rule(Box::new(MaxUncertaintyThreshold::new(threshold)));
+    }
+
+    /// Block when a feature weighted above its tolerance is missing, e.g. from mRMR scores.
+    /// Entries are (name, weight, tolerance) or (name, weight), the latter using `tolerance`.
+    #[pyo3(signature = (weighted_features, tolerance=None))]
+    fn add_critical_features(&mut self, weighted_features: Vec<WeightedFeature>, tolerance: Option<f64>) -> PyResult<()> {
+        let features = weighted_features
+            .into_iter()
+            .map(|feature| match feature {
+                WeightedFeature::WithTolerance(name, weight, tolerance) => Ok((name, weight, tolerance)),
+                WeightedFeature::Weighted(name, weight) => tolerance
+                    .map(|tolerance| (name.clone(), weight, tolerance))
+                    .ok_or_else(|| PyValueError::new_err(format!("No tolerance given for feature '{}'", name))),
+            })
+            .collect::<PyResult<Vec<_>>>()?;
+        let features = features.iter().map(|(name, weight, tolerance)| (name.as_str(), *weight, *tolerance)).collect();
+        self.inner.add_rule(Box::new(CriticalFeatureMissingRule::new(features)));
+        Ok(())
+    }
+
+    /// Active rules in evaluation order, as (id, description) pairs
+    fn rules(&self) -> Vec<(String, String)> {
+        self.inner.rules().into_iter().map(|rule| (rule.id, rule.description)).collect()
+    }
+
+    fn has_rule(&self, id: &str) -> bool {
+        self.inner.has_rule(id)
+    }
+
+    fn remove_rule(&mut self, id: &str) -> bool {
+        self.inner.remove_rule(id)
+    }
+
+    /// The first violated rule's explanation, or None if the action may proceed
+    fn check(&self, data: &PyPatientData) -> Option<PyCounterfactualExplanation> {
+        self.inner.check(&data.inner, ())
+            .explanation()
+            .map(|e| PyCounterfactualExplanation { inner: e.clone() })
+    }
+
+    /// Explanations for every violated rule, as dicts
+    fn check_all(&self, py: Python, data: &PyPatientData) -> PyResult<Vec<PyObject>> {
+        self.inner.check_all(&data.inner)
+            .iter()
+            .map(|e| explanation_dict(py, e))
+            .collect()
+    }
+}
+
+pub fn register(m: &PyModule) -> PyResult<()> {
+    m.add_class::<PyPatientData>()?;
+    m.add_class::<PyCounterfactualExplanation>()?;
+    m.add_class::<PyEthosGuard>()?;
+    Ok(())
+}
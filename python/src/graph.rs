@@ -0,0 +1,125 @@
+//! Python wrapper for the backend's causal graph builder
+//!
+//! Renders discovered structures with the same DOT generator and themes as the CLI.
+
+use std::time::Duration;
+
+use pyo3::prelude::*;
+use icu_sepsis_backend::visualization::{CausalGraph, EdgeType, GraphTheme, NodeType};
+
+use crate::{backend_err, FeatureRanking};
+
+/// A selected feature given either as a FeatureRanking or a (name, score) pair
+#[derive(FromPyObject)]
+enum FeatureScore {
+    Ranking(FeatureRanking),
+    Pair(String, f64),
+}
+
+fn parse<T: std::str::FromStr<Err = String>>(value: &str) -> PyResult<T> {
+    value.parse().map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)
+}
+
+/// Causal graph of features, mechanisms and a target, exportable as DOT, JSON or SVG
+#[pyclass(name = "CausalGraph")]
+#[derive(Clone)]
+pub struct PyCausalGraph {
+    inner: CausalGraph,
+}
+
+#[pymethods]
+impl PyCausalGraph {
+    /// An empty graph; add nodes and edges with `add_node`/`add_edge`
+    #[new]
+    fn new(title: String) -> Self {
+        Self { inner: CausalGraph::new(title) }
+    }
+
+    /// Star graph of mRMR features pointing at the target, weighted by score.
+    /// `features` is a list of FeatureRanking or (name, score) pairs, as returned by `run_mrmr`.
+    #[staticmethod]
+    fn from_mrmr_results(features: Vec<FeatureScore>, target: &str) -> Self {
+        let features: Vec<(String, f64)> = features.into_iter()
+            .map(|feature| match feature {
+                FeatureScore::Ranking(ranking) => (ranking.name, ranking.score),
+                FeatureScore::Pair(name, score) => (name, score),
+            })
+            .collect();
+        Self { inner: CausalGraph::from_mrmr_results(&features, target) }
+    }
+
+    /// node_type: "feature", "target", "latent" or "mechanism"
+    #[pyo3(signature = (id, label, node_type="feature", score=None))]
+    fn add_node(&mut self, id: String, label: String, node_type: &str, score: Option<f64>) -> PyResult<()> {
+        let node_type: NodeType = parse(node_type)?;
+        match score {
+            Some(score) => self.inner.add_node_with_score(id, label, node_type, score),
+            None => self.inner.add_node(id, label, node_type),
+        };
+        Ok(())
+    }
+
+    /// edge_type: "causal", "redundant", "synergistic" or "association"
+    #[pyo3(signature = (from_id, to_id, weight, edge_type="causal"))]
+    fn add_edge(&mut self, from_id: String, to_id: String, weight: f64, edge_type: &str) -> PyResult<()> {
+        let edge_type: EdgeType = parse(edge_type)?;
+        self.inner.add_edge(from_id, to_id, weight, edge_type);
+        Ok(())
+    }
+
+    /// Box the nodes labelled with these feature names together
+    fn add_cluster(&mut self, label: String, features: Vec<String>) {
+        self.inner.add_cluster(label, &features);
+    }
+
+    /// Graphviz DOT source; theme is "dark", "light" or "print"
+    #[pyo3(signature = (theme="dark"))]
+    fn to_dot(&self, theme: &str) -> PyResult<String> {
+        Ok(self.inner.to_dot_themed(parse(theme)?))
+    }
+
+    /// JSON for web visualization
+    fn to_json(&self) -> PyResult<String> {
+        self.inner.to_json().map_err(backend_err)
+    }
+
+    /// SVG markup rendered by the Graphviz `dot` binary, which must be installed
+    #[pyo3(signature = (theme="dark", timeout=30.0))]
+    fn to_svg(&self, py: Python, theme: &str, timeout: f64) -> PyResult<String> {
+        let theme: GraphTheme = parse(theme)?;
+        if !(timeout.is_finite() && timeout > 0.0) {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("timeout must be a positive number of seconds"));
+        }
+        py.allow_threads(|| self.inner.to_svg(theme, Duration::from_secs_f64(timeout)))
+            .map_err(backend_err)
+    }
+
+    /// Node ids in insertion order
+    #[getter]
+    fn node_ids(&self) -> Vec<String> {
+        self.inner.nodes.iter().map(|n| n.id.clone()).collect()
+    }
+
+    /// (from, to, weight) for every edge
+    #[getter]
+    fn edges(&self) -> Vec<(String, String, f64)> {
+        self.inner.edges.iter().map(|e| (e.from.clone(), e.to.clone(), e.weight)).collect()
+    }
+
+    /// SVG rendering for Jupyter; None (plain repr) when Graphviz isn't available
+    fn _repr_svg_(&self, py: Python) -> Option<String> {
+        self.to_svg(py, "light", 30.0).ok()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "CausalGraph(title='{}', nodes={}, edges={})",
+            self.inner.title, self.inner.nodes.len(), self.inner.edges.len()
+        )
+    }
+}
+
+pub fn register(m: &PyModule) -> PyResult<()> {
+    m.add_class::<PyCausalGraph>()?;
+    Ok(())
+}
@@ -3,35 +3,231 @@
 //! Provides Python access to:
 //! - mRMR feature selection
 //! - SURD causal decomposition
+//! - Transfer entropy between lagged time series
 //! - Causaloid graph construction
+//! - Ethos compliance guardrails
+//! - A combined selection → SURD → graph pipeline
+//! - Datasets kept resident in Rust across calls
+//! - Chunked ingestion of datasets larger than memory
+//! - A scikit-learn compatible mRMR feature selector
 
+mod dataset;
+mod ethos;
+mod graph;
+mod pipeline;
+mod selector;
+mod serialization;
+mod streaming;
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyException;
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList};
+use numpy::PyReadonlyArray2;
+use pyo3_polars::PyDataFrame;
 use polars::prelude::*;
 use anyhow::Result;
-use deep_causality_algorithms::mrmr::mrmr_features_selector;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::time::Duration;
+use serde::{Serialize, Deserialize};
+use deep_causality_algorithms::surd::surd_states;
 use deep_causality_tensor::CausalTensor;
+use icu_sepsis_backend::causality::{
+    CausalDiscovery, DiscretizationStrategy, Discretizer, FeatureSurdTerms, MrmrOptions, ProgressHook,
+    SurdAnalysisResult, SurdOptions, TransferEntropyOptions,
+};
+use icu_sepsis_backend::data::profile::{profile_tensor, ColumnProfile};
+use icu_sepsis_backend::error::DeepCausalityError as BackendError;
+use icu_sepsis_backend::utils::tensor_adapter::TensorAdapter;
+
+// Base class for every error raised by the bindings; the rest narrow down what failed
+create_exception!(_core, DeepCausalityError, PyException);
+create_exception!(_core, MrmrError, DeepCausalityError, "mRMR feature selection failed or selected nothing");
+create_exception!(_core, SurdError, DeepCausalityError, "SURD decomposition failed");
+create_exception!(_core, TensorShapeError, DeepCausalityError, "Input data is empty, ragged or doesn't match its column names");
+create_exception!(_core, MissingColumnError, DeepCausalityError, "A named target or filter column is not in the data");
+create_exception!(_core, ColumnTypeError, TensorShapeError, "A column holds values that are not numbers, e.g. strings");
+
+/// Raise a backend error as the matching Python exception
+fn backend_err(e: BackendError) -> PyErr {
+    let message = e.to_string();
+    match e {
+        BackendError::TargetNotFound(_) => MissingColumnError::new_err(message),
+        BackendError::TensorConversion(_) | BackendError::Schema { .. } => TensorShapeError::new_err(message),
+        BackendError::Mrmr(_) | BackendError::NoFeaturesSelected(_) => MrmrError::new_err(message),
+        BackendError::Surd(_) => SurdError::new_err(message),
+        BackendError::Config(_) => PyErr::new::<pyo3::exceptions::PyValueError, _>(message),
+        _ => DeepCausalityError::new_err(message),
+    }
+}
 
 /// Result from mRMR feature selection
-#[pyclass]
-#[derive(Clone)]
+#[pyclass(module = "deep_causality._core")]
+#[derive(Clone, Serialize, Deserialize)]
 struct FeatureRanking {
     #[pyo3(get)]
     name: String,
+    /// 1-based position in selection order
+    #[pyo3(get)]
+    rank: usize,
     #[pyo3(get)]
     score: f64,
+    /// Score relative to the top feature, set when normalize_scores=True
+    #[pyo3(get)]
+    normalized_score: Option<f64>,
+    /// F-statistic against the target
+    #[pyo3(get)]
+    relevance: Option<f64>,
+    /// Redundancy with the features selected before it (0 for the first)
+    #[pyo3(get)]
+    redundancy: Option<f64>,
+    /// Mean score over the bootstrap resamples that selected the feature, set when bootstrap=N;
+    /// relative to the top feature's score like `normalized_score` when scores are normalized
+    #[pyo3(get)]
+    bootstrap_mean: Option<f64>,
+    /// Standard deviation of those scores
+    #[pyo3(get)]
+    bootstrap_std: Option<f64>,
+    /// Fraction of bootstrap resamples that selected the feature
+    #[pyo3(get)]
+    selection_frequency: Option<f64>,
 }
 
 #[pymethods]
 impl FeatureRanking {
     fn __repr__(&self) -> String {
-        format!("FeatureRanking(name='{}', score={:.4})", self.name, self.score)
+        match self.normalized_score {
+            Some(normalized) => format!(
+                "FeatureRanking(rank={}, name='{}', score={:.4}, normalized_score={:.4})",
+                self.rank, self.name, self.score, normalized
+            ),
+            None => format!("FeatureRanking(rank={}, name='{}', score={:.4})", self.rank, self.name, self.score),
+        }
+    }
+}
+
+/// How one candidate scored in a single mRMR selection round
+#[pyclass(module = "deep_causality._core")]
+#[derive(Clone, Serialize, Deserialize)]
+struct CandidateScore {
+    #[pyo3(get)]
+    name: String,
+    #[pyo3(get)]
+    relevance: f64,
+    #[pyo3(get)]
+    redundancy: f64,
+    #[pyo3(get)]
+    score: f64,
+}
+
+#[pymethods]
+impl CandidateScore {
+    fn __repr__(&self) -> String {
+        format!(
+            "CandidateScore(name='{}', score={:.4}, relevance={:.4}, redundancy={:.4})",
+            self.name, self.score, self.relevance, self.redundancy
+        )
+    }
+}
+
+/// Information transfer from one feature at one lag to the target
+#[pyclass(module = "deep_causality._core")]
+#[derive(Clone, Serialize, Deserialize)]
+struct TransferEntropyScore {
+    #[pyo3(get)]
+    feature: String,
+    /// Rows between the feature's value and the target value it informs
+    #[pyo3(get)]
+    lag: usize,
+    /// Bits the lagged feature adds about the target beyond the target's previous value
+    #[pyo3(get)]
+    transfer_entropy: f64,
+    #[pyo3(get)]
+    n_samples: usize,
+}
+
+#[pymethods]
+impl TransferEntropyScore {
+    fn __repr__(&self) -> String {
+        format!(
+            "TransferEntropyScore(feature='{}', lag={}, transfer_entropy={:.4}, n_samples={})",
+            self.feature, self.lag, self.transfer_entropy, self.n_samples
+        )
+    }
+}
+
+/// One mRMR selection round: the feature picked and every candidate's score
+#[pyclass(module = "deep_causality._core")]
+#[derive(Clone, Serialize, Deserialize)]
+struct SelectionStep {
+    /// 1-based selection round
+    #[pyo3(get)]
+    round: usize,
+    #[pyo3(get)]
+    selected: String,
+    /// Candidates still in the pool this round, in column order
+    #[pyo3(get)]
+    candidates: Vec<CandidateScore>,
+}
+
+#[pymethods]
+impl SelectionStep {
+    /// Candidate name -> score for this round, e.g. for plotting marginal gains
+    fn scores(&self) -> HashMap<String, f64> {
+        self.candidates.iter().map(|c| (c.name.clone(), c.score)).collect()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "SelectionStep(round={}, selected='{}', candidates={})",
+            self.round, self.selected, self.candidates.len()
+        )
+    }
+}
+
+impl SelectionStep {
+    /// The picked feature's own scores
+    fn chosen(&self) -> Option<&CandidateScore> {
+        self.candidates.iter().find(|c| c.name == self.selected)
+    }
+}
+
+impl From<icu_sepsis_backend::causality::SelectionStep> for SelectionStep {
+    fn from(step: icu_sepsis_backend::causality::SelectionStep) -> Self {
+        Self {
+            round: step.round,
+            selected: step.selected,
+            candidates: step.candidates.into_iter()
+                .map(|c| CandidateScore { name: c.name, relevance: c.relevance, redundancy: c.redundancy, score: c.score })
+                .collect(),
+        }
+    }
+}
+
+/// Selected features together with the score of every candidate at every step
+#[pyclass(module = "deep_causality._core")]
+#[derive(Clone, Serialize, Deserialize)]
+struct MrmrFullResult {
+    /// Selected features, best first
+    #[pyo3(get)]
+    features: Vec<FeatureRanking>,
+    /// One entry per selection round
+    #[pyo3(get)]
+    steps: Vec<SelectionStep>,
+}
+
+#[pymethods]
+impl MrmrFullResult {
+    fn __repr__(&self) -> String {
+        format!("MrmrFullResult(features={}, steps={})", self.features.len(), self.steps.len())
     }
 }
 
 /// Result from SURD analysis
-#[pyclass]
-#[derive(Clone)]
+#[pyclass(module = "deep_causality._core")]
+#[derive(Clone, Serialize, Deserialize)]
 struct SurdResult {
     #[pyo3(get)]
     redundant_info: f64,
@@ -41,6 +237,35 @@ struct SurdResult {
     synergistic_info: f64,
     #[pyo3(get)]
     total_info: f64,
+    /// Feature name -> {"redundant", "unique", "synergistic"} share of the totals
+    #[pyo3(get)]
+    per_feature: HashMap<String, HashMap<String, f64>>,
+}
+
+/// Per-feature SURD terms as nested dicts for Python
+fn per_feature_dicts(terms: &BTreeMap<String, FeatureSurdTerms>) -> HashMap<String, HashMap<String, f64>> {
+    terms.iter()
+        .map(|(name, t)| {
+            let parts = HashMap::from([
+                ("redundant".to_string(), t.redundant),
+                ("unique".to_string(), t.unique),
+                ("synergistic".to_string(), t.synergistic),
+            ]);
+            (name.clone(), parts)
+        })
+        .collect()
+}
+
+impl From<SurdAnalysisResult> for SurdResult {
+    fn from(result: SurdAnalysisResult) -> Self {
+        Self {
+            redundant_info: result.redundant_info,
+            unique_info: result.unique_info,
+            synergistic_info: result.synergistic_info,
+            total_info: result.total_info,
+            per_feature: per_feature_dicts(&result.per_feature),
+        }
+    }
 }
 
 #[pymethods]
@@ -58,105 +283,748 @@ impl SurdResult {
         dict.set_item("unique_info", self.unique_info)?;
         dict.set_item("synergistic_info", self.synergistic_info)?;
         dict.set_item("total_info", self.total_info)?;
+        dict.set_item("per_feature", self.per_feature.clone())?;
         Ok(dict.into())
     }
 }
 
+/// None and NaN both mark a missing value
+fn missing_as_none(value: Option<f64>) -> Option<f64> {
+    value.filter(|v| !v.is_nan())
+}
+
+/// Result from SURD dual analysis (Sepsis vs Non-Sepsis)
+#[pyclass(module = "deep_causality._core")]
+#[derive(Clone, Serialize, Deserialize)]
+struct SurdDualResult {
+    #[pyo3(get)]
+    sepsis_result: SurdResult,
+    #[pyo3(get)]
+    non_sepsis_result: SurdResult,
+    /// Drivers selected for the sepsis subset only
+    #[pyo3(get)]
+    disjoint_drivers: Vec<String>,
+    /// Drivers selected for both subsets
+    #[pyo3(get)]
+    shared_drivers: Vec<String>,
+    /// How far the sepsis drivers' information profile differs from the non-sepsis one
+    #[pyo3(get)]
+    sepsis_specific_score: f64,
+}
+
+#[pymethods]
+impl SurdDualResult {
+    fn __repr__(&self) -> String {
+        format!(
+            "SurdDualResult(disjoint_drivers={:?}, shared_drivers={:?}, sepsis_specific_score={:.4})",
+            self.disjoint_drivers, self.shared_drivers, self.sepsis_specific_score
+        )
+    }
+}
+
+serialization::impl_serialization!(
+    FeatureRanking, CandidateScore, SelectionStep, MrmrFullResult, SurdResult, SurdDualResult, TransferEntropyScore,
+);
+
 /// Convert Python list of lists to CausalTensor
-fn py_data_to_tensor(data: Vec<Vec<f64>>) -> Result<(CausalTensor<Option<f64>>, usize, usize), PyErr> {
+fn py_data_to_tensor(data: Vec<Vec<Option<f64>>>) -> Result<(CausalTensor<Option<f64>>, usize, usize), PyErr> {
     if data.is_empty() {
-        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("Empty data"));
+        return Err(TensorShapeError::new_err("Empty data"));
     }
     
-    let n_rows = data.len();
-    let n_cols = data[0].len();
+    // Ragged rows would otherwise index out of bounds below
+    let (n_rows, n_cols) = TensorAdapter::check_rectangular(&data)
+        .map_err(|e| TensorShapeError::new_err(e.to_string()))?;
     
     // Flatten in column-major order (for compatibility with deep_causality)
     let mut flat_data: Vec<Option<f64>> = Vec::with_capacity(n_rows * n_cols);
     for col_idx in 0..n_cols {
         for row in &data {
-            flat_data.push(Some(row[col_idx]));
+            flat_data.push(missing_as_none(row[col_idx]));
         }
     }
     
-    let tensor = CausalTensor::new(flat_data, vec![n_rows, n_cols])
-        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{:?}", e)))?;
+    let tensor = TensorAdapter::column_major_tensor(flat_data, n_rows, n_cols)
+        .map_err(|e| TensorShapeError::new_err(e.to_string()))?;
     
     Ok((tensor, n_rows, n_cols))
 }
 
+/// Copy a 2D float64 NumPy array (rows x columns) into a column-major tensor
+fn numpy_to_tensor(array: &PyReadonlyArray2<f64>) -> PyResult<CausalTensor<Option<f64>>> {
+    let view = array.as_array();
+    let (n_rows, n_cols) = view.dim();
+    if n_rows == 0 {
+        return Err(TensorShapeError::new_err("Empty data"));
+    }
+
+    // Iterating the transpose walks column by column, i.e. column-major order.
+    // The array buffer belongs to Python, so it is copied out before releasing the GIL.
+    let flat_data: Vec<Option<f64>> = view.t().iter().map(|&v| missing_as_none(Some(v))).collect();
+    TensorAdapter::column_major_tensor(flat_data, n_rows, n_cols)
+        .map_err(|e| TensorShapeError::new_err(e.to_string()))
+}
+
+/// Build a DataFrame from row-major Python data for the backend's frame-based analyses
+fn py_data_to_frame(data: &[Vec<Option<f64>>], column_names: &[String]) -> PyResult<DataFrame> {
+    let (_, n_cols) = TensorAdapter::check_rectangular(data)
+        .map_err(|e| TensorShapeError::new_err(e.to_string()))?;
+    if data.is_empty() {
+        return Err(TensorShapeError::new_err("Empty data"));
+    }
+    if column_names.len() != n_cols {
+        return Err(TensorShapeError::new_err(format!(
+            "Got {} column names but data has {} columns",
+            column_names.len(), n_cols
+        )));
+    }
+
+    let columns: Vec<Series> = column_names.iter()
+        .enumerate()
+        .map(|(j, name)| {
+            let values: Vec<Option<f64>> = data.iter().map(|row| missing_as_none(row[j])).collect();
+            Series::new(name, values)
+        })
+        .collect();
+    DataFrame::new(columns).map_err(|e| TensorShapeError::new_err(e.to_string()))
+}
+
+/// The binning requested by the `n_bins`/`discretization` keyword arguments, if any
+fn parse_discretizer(n_bins: usize, discretization: Option<&str>) -> PyResult<Option<Discretizer>> {
+    discretization
+        .map(|strategy| {
+            let strategy: DiscretizationStrategy = strategy.parse()
+                .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?;
+            Ok(Discretizer::new(n_bins, strategy))
+        })
+        .transpose()
+}
+
+/// Column indices (targets included) left after include/exclude filters, or None without filters
+fn candidate_columns(
+    column_names: &[String],
+    targets: &[String],
+    include: Option<&[String]>,
+    exclude: Option<&[String]>,
+) -> PyResult<Option<Vec<usize>>> {
+    // `allow` is true when `listed` is an allow-list
+    let (listed, allow) = match (include, exclude) {
+        (None, None) => return Ok(None),
+        (Some(include), None) => (include, true),
+        (None, Some(exclude)) => (exclude, false),
+        (Some(_), Some(_)) => {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "include_columns and exclude_columns cannot both be set"
+            ));
+        }
+    };
+
+    if let Some(missing) = listed.iter().find(|name| !column_names.contains(*name)) {
+        return Err(MissingColumnError::new_err(
+            format!("Filtered column '{}' not found", missing)
+        ));
+    }
+
+    Ok(Some((0..column_names.len())
+        .filter(|&i| targets.contains(&column_names[i]) || listed.contains(&column_names[i]) == allow)
+        .collect()))
+}
+
+/// Copy the given columns of a column-major tensor into a new tensor
+fn select_columns(tensor: &CausalTensor<Option<f64>>, keep: &[usize]) -> PyResult<CausalTensor<Option<f64>>> {
+    let height = tensor.shape()[0];
+    let data: Vec<Option<f64>> = keep.iter()
+        .flat_map(|&i| tensor.as_slice()[i * height..(i + 1) * height].iter().copied())
+        .collect();
+    CausalTensor::new(data, vec![height, keep.len()])
+        .map_err(|e| TensorShapeError::new_err(format!("{:?}", e)))
+}
+
+/// One target column name, or a list of them for a multi-target run
+#[derive(FromPyObject)]
+enum Targets {
+    One(String),
+    Many(Vec<String>),
+}
+
 /// Run mRMR (Minimum Redundancy Maximum Relevance) feature selection
 ///
 /// Args:
-///     data: 2D list of floats (rows x columns)
+///     data: 2D list of floats (rows x columns); None or NaN marks a missing value
 ///     column_names: List of column names
-///     target_column: Name of the target column
+///     target_column: Name of the target column, or a list of target columns to rank
+///         features for all of them in one pass (no target is a candidate for another)
 ///     max_features: Maximum number of features to select
+///     min_score: Stop selecting once the next feature scores below this cutoff
+///     include_columns: Only consider these columns as candidates
+///     exclude_columns: Never select these columns (cannot be combined with include_columns)
+///     normalize_scores: Also report each score relative to the top feature (0..1)
+///     n_bins: Number of bins when discretization is set (default: 10)
+///     discretization: "equal_width", "equal_frequency" or "kmeans" to bin features first, as the backend does
+///     progress_callback: Called as callback(features_selected, percent_complete); exceptions it
+///         raises abort the run. The default selector reports when each target starts and
+///         finishes, repeating the last report every 0.25 s in between; options that use the
+///         native selector (n_threads, sample_weights) report after every selection round
+///     n_threads: Score candidates in parallel on this many threads (0 = one per core), using
+///         the native selector; by default the upstream selector runs on a single thread
+///     bootstrap: Rerun selection on this many row resamples (drawn with replacement, in
+///         parallel) and fill in each feature's bootstrap_mean, bootstrap_std and
+///         selection_frequency. Resamples don't report progress
+///     random_seed: Seed for the bootstrap resamples; the same seed gives the same statistics
+///     sample_weights: One non-negative weight per row, applied to every correlation so a
+///         rare class (e.g. septic rows) isn't drowned out; cannot be combined with bootstrap
 ///
 /// Returns:
-///     List of FeatureRanking objects, sorted by importance
+///     List of FeatureRanking objects, sorted by importance; for a list of targets,
+///     a dict mapping each target to its list
+// Python keyword arguments map one-to-one onto parameters
+#[allow(clippy::too_many_arguments)]
 #[pyfunction]
-#[pyo3(signature = (data, column_names, target_column, max_features=10))]
+#[pyo3(signature = (data, column_names, target_column, max_features=10, min_score=None, include_columns=None, exclude_columns=None, normalize_scores=false, n_bins=10, discretization=None, progress_callback=None, n_threads=None, bootstrap=None, random_seed=None, sample_weights=None))]
 fn run_mrmr(
-    data: Vec<Vec<f64>>,
+    py: Python,
+    data: Vec<Vec<Option<f64>>>,
+    column_names: Vec<String>,
+    target_column: Targets,
+    max_features: usize,
+    min_score: Option<f64>,
+    include_columns: Option<Vec<String>>,
+    exclude_columns: Option<Vec<String>>,
+    normalize_scores: bool,
+    n_bins: usize,
+    discretization: Option<String>,
+    progress_callback: Option<PyObject>,
+    n_threads: Option<usize>,
+    bootstrap: Option<usize>,
+    random_seed: Option<u64>,
+    sample_weights: Option<Vec<f64>>,
+) -> PyResult<PyObject> {
+    let (targets, multi) = match target_column {
+        Targets::One(target) => (vec![target], false),
+        Targets::Many(targets) => (targets, true),
+    };
+
+    // Arguments are already extracted into Rust values, so the GIL isn't needed from here on
+    let mut results = py.allow_threads(|| {
+        let (tensor, _, _) = py_data_to_tensor(data)?;
+        mrmr_on_tensor_targets(
+            tensor, column_names, targets, Vec::new(), max_features, min_score,
+            include_columns, exclude_columns, normalize_scores, n_bins, discretization, progress_callback, n_threads,
+            bootstrap, random_seed, sample_weights, false,
+        )
+    })?;
+
+    if !multi {
+        return Ok(results.remove(0).1.features.into_py(py));
+    }
+    let by_target = PyDict::new(py);
+    for (target, result) in results {
+        by_target.set_item(target, result.features.into_py(py))?;
+    }
+    Ok(by_target.into())
+}
+
+/// Run mRMR and also return every candidate's score at each selection step
+///
+/// Takes the same arguments as `run_mrmr`; useful for plotting the marginal gain
+/// curve when choosing how many features to keep. Recording every candidate needs the
+/// native selector, so this always uses it, as `run_mrmr` does with `n_threads`.
+///
+/// Returns:
+///     MrmrFullResult with the selected features and one SelectionStep per round
+#[allow(clippy::too_many_arguments)]
+#[pyfunction]
+#[pyo3(signature = (data, column_names, target_column, max_features=10, min_score=None, include_columns=None, exclude_columns=None, normalize_scores=false, n_bins=10, discretization=None, progress_callback=None, n_threads=None))]
+fn run_mrmr_full(
+    py: Python,
+    data: Vec<Vec<Option<f64>>>,
+    column_names: Vec<String>,
+    target_column: String,
+    max_features: usize,
+    min_score: Option<f64>,
+    include_columns: Option<Vec<String>>,
+    exclude_columns: Option<Vec<String>>,
+    normalize_scores: bool,
+    n_bins: usize,
+    discretization: Option<String>,
+    progress_callback: Option<PyObject>,
+    n_threads: Option<usize>,
+) -> PyResult<MrmrFullResult> {
+    py.allow_threads(|| {
+        let (tensor, _, _) = py_data_to_tensor(data)?;
+        mrmr_on_tensor(
+            tensor, column_names, target_column, max_features, min_score,
+            include_columns, exclude_columns, normalize_scores, n_bins, discretization, progress_callback, n_threads,
+            true,
+        )
+    })
+}
+
+/// Run mRMR conditioned on known covariates (partial mRMR)
+///
+/// The target and every candidate are first replaced by their residuals from a linear
+/// fit on `condition_on`, so relevance and redundancy become partial correlations and
+/// features that only echo the covariates (e.g. age, ICU length of stay) drop down the
+/// ranking. The covariates themselves are never selected; rows missing a covariate
+/// are treated as missing. Takes the remaining keyword options of `run_mrmr`.
+///
+/// Returns:
+///     List of FeatureRanking objects, sorted by importance
+#[allow(clippy::too_many_arguments)]
+#[pyfunction]
+#[pyo3(signature = (data, column_names, target_column, condition_on, max_features=10, min_score=None, include_columns=None, exclude_columns=None, normalize_scores=false, n_bins=10, discretization=None, progress_callback=None, n_threads=None))]
+fn run_mrmr_conditional(
+    py: Python,
+    data: Vec<Vec<Option<f64>>>,
     column_names: Vec<String>,
     target_column: String,
+    condition_on: Vec<String>,
     max_features: usize,
+    min_score: Option<f64>,
+    include_columns: Option<Vec<String>>,
+    exclude_columns: Option<Vec<String>>,
+    normalize_scores: bool,
+    n_bins: usize,
+    discretization: Option<String>,
+    progress_callback: Option<PyObject>,
+    n_threads: Option<usize>,
 ) -> PyResult<Vec<FeatureRanking>> {
-    // Find target column index
-    let target_idx = column_names.iter()
-        .position(|n| n == &target_column)
-        .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>(
-            format!("Target column '{}' not found", target_column)
-        ))?;
+    py.allow_threads(|| {
+        let (tensor, _, _) = py_data_to_tensor(data)?;
+        let mut results = mrmr_on_tensor_targets(
+            tensor, column_names, vec![target_column], condition_on, max_features, min_score,
+            include_columns, exclude_columns, normalize_scores, n_bins, discretization, progress_callback, n_threads,
+            None, None, None, false,
+        )?;
+        Ok(results.remove(0).1.features)
+    })
+}
 
-    // Convert to tensor
-    let (tensor, _, _) = py_data_to_tensor(data)?;
+/// Run mRMR on a 2D NumPy array (rows x columns) of float64
+///
+/// Reads the array buffer straight into the tensor, skipping the list-of-lists
+/// conversion; takes the same keyword options as `run_mrmr`.
+#[allow(clippy::too_many_arguments)]
+#[pyfunction]
+#[pyo3(signature = (array, column_names, target_column, max_features=10, min_score=None, include_columns=None, exclude_columns=None, normalize_scores=false, n_bins=10, discretization=None, progress_callback=None, n_threads=None, bootstrap=None, random_seed=None, sample_weights=None))]
+fn run_mrmr_numpy(
+    py: Python,
+    array: PyReadonlyArray2<f64>,
+    column_names: Vec<String>,
+    target_column: String,
+    max_features: usize,
+    min_score: Option<f64>,
+    include_columns: Option<Vec<String>>,
+    exclude_columns: Option<Vec<String>>,
+    normalize_scores: bool,
+    n_bins: usize,
+    discretization: Option<String>,
+    progress_callback: Option<PyObject>,
+    n_threads: Option<usize>,
+    bootstrap: Option<usize>,
+    random_seed: Option<u64>,
+    sample_weights: Option<Vec<f64>>,
+) -> PyResult<Vec<FeatureRanking>> {
+    let tensor = numpy_to_tensor(&array)?;
+    py.allow_threads(|| mrmr_on_tensor_targets(
+        tensor, column_names, vec![target_column], Vec::new(), max_features, min_score,
+        include_columns, exclude_columns, normalize_scores, n_bins, discretization, progress_callback, n_threads,
+        bootstrap, random_seed, sample_weights, false,
+    ))
+    .map(|mut results| results.remove(0).1.features)
+}
 
-    // Run mRMR
-    let selected = mrmr_features_selector(&tensor, max_features, target_idx)
-        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("{:?}", e)))?;
+/// Run mRMR feature selection on a Polars DataFrame
+///
+/// The frame crosses over through the Arrow C data interface, so nothing is copied
+/// into Python lists; nulls stay missing and any numeric dtype is accepted.
+///
+/// Args:
+///     df: Polars DataFrame with numeric columns
+///     target: Name of the target column
+///     max_features: Maximum number of features to select (default: 10)
+///     (remaining keyword options as in `run_mrmr`)
+///
+/// Returns:
+///     List of FeatureRanking objects, sorted by importance
+#[allow(clippy::too_many_arguments)]
+#[pyfunction]
+#[pyo3(signature = (df, target, max_features=10, min_score=None, include_columns=None, exclude_columns=None, normalize_scores=false, n_bins=10, discretization=None, progress_callback=None, n_threads=None, bootstrap=None, random_seed=None, sample_weights=None))]
+fn run_mrmr_polars(
+    py: Python,
+    df: PyDataFrame,
+    target: String,
+    max_features: usize,
+    min_score: Option<f64>,
+    include_columns: Option<Vec<String>>,
+    exclude_columns: Option<Vec<String>>,
+    normalize_scores: bool,
+    n_bins: usize,
+    discretization: Option<String>,
+    progress_callback: Option<PyObject>,
+    n_threads: Option<usize>,
+    bootstrap: Option<usize>,
+    random_seed: Option<u64>,
+    sample_weights: Option<Vec<f64>>,
+) -> PyResult<Vec<FeatureRanking>> {
+    let df: DataFrame = df.into();
+    if df.height() == 0 {
+        return Err(TensorShapeError::new_err("Empty data"));
+    }
 
-    // Map back to names
-    let results: Vec<FeatureRanking> = selected.into_iter()
-        .map(|(idx, score)| FeatureRanking {
-            name: column_names[idx].clone(),
-            score,
-        })
+    py.allow_threads(|| {
+        let (tensor, column_names) = TensorAdapter::df_to_tensor(&df)
+            .map_err(|e| DeepCausalityError::new_err(e.to_string()))?;
+        mrmr_on_tensor_targets(
+            tensor, column_names, vec![target], Vec::new(), max_features, min_score,
+            include_columns, exclude_columns, normalize_scores, n_bins, discretization, progress_callback, n_threads,
+            bootstrap, random_seed, sample_weights, false,
+        )
+        .map(|mut results| results.remove(0).1.features)
+    })
+}
+
+/// Shared mRMR path once the input is a column-major tensor; `trace` records every
+/// candidate's score in every round, which needs the native selector
+#[allow(clippy::too_many_arguments)]
+fn mrmr_on_tensor(
+    tensor: CausalTensor<Option<f64>>,
+    column_names: Vec<String>,
+    target_column: String,
+    max_features: usize,
+    min_score: Option<f64>,
+    include_columns: Option<Vec<String>>,
+    exclude_columns: Option<Vec<String>>,
+    normalize_scores: bool,
+    n_bins: usize,
+    discretization: Option<String>,
+    progress_callback: Option<PyObject>,
+    n_threads: Option<usize>,
+    trace: bool,
+) -> PyResult<MrmrFullResult> {
+    let mut results = mrmr_on_tensor_targets(
+        tensor, column_names, vec![target_column], Vec::new(), max_features, min_score,
+        include_columns, exclude_columns, normalize_scores, n_bins, discretization, progress_callback, n_threads,
+        None, None, None, trace,
+    )?;
+    Ok(results.remove(0).1)
+}
+
+/// [`mrmr_on_tensor`] for several targets in one pass over the shared tensor, returning
+/// (target, result) in the given order; no target is a candidate for another
+#[allow(clippy::too_many_arguments)]
+fn mrmr_on_tensor_targets(
+    tensor: CausalTensor<Option<f64>>,
+    column_names: Vec<String>,
+    targets: Vec<String>,
+    condition_on: Vec<String>,
+    max_features: usize,
+    min_score: Option<f64>,
+    include_columns: Option<Vec<String>>,
+    exclude_columns: Option<Vec<String>>,
+    normalize_scores: bool,
+    n_bins: usize,
+    discretization: Option<String>,
+    progress_callback: Option<PyObject>,
+    n_threads: Option<usize>,
+    bootstrap: Option<usize>,
+    random_seed: Option<u64>,
+    sample_weights: Option<Vec<f64>>,
+    trace: bool,
+) -> PyResult<Vec<(String, MrmrFullResult)>> {
+    if targets.is_empty() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("No target columns given"));
+    }
+    if let Some(missing) = targets.iter().find(|t| !column_names.contains(*t)) {
+        return Err(MissingColumnError::new_err(format!("Target column '{}' not found", missing)));
+    }
+    if let Some(missing) = condition_on.iter().find(|c| !column_names.contains(*c)) {
+        return Err(MissingColumnError::new_err(format!("Conditioning column '{}' not found", missing)));
+    }
+    // Checked before selection so the conflict doesn't surface only after a full run
+    if bootstrap.is_some() && sample_weights.is_some() {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            "bootstrap cannot be combined with sample_weights"
+        ));
+    }
+    let n_cols = tensor.shape()[1];
+
+    // A mismatch would silently attach scores to the wrong feature names
+    if column_names.len() != n_cols {
+        return Err(TensorShapeError::new_err(format!(
+            "Got {} column names but data has {} columns",
+            column_names.len(), n_cols
+        )));
+    }
+
+    // Narrow the candidate pool when a column filter is given; covariates stay for conditioning
+    let kept = [targets.as_slice(), condition_on.as_slice()].concat();
+    let (tensor, column_names) = match candidate_columns(
+        &column_names, &kept, include_columns.as_deref(), exclude_columns.as_deref(),
+    )? {
+        Some(keep) => {
+            let names = keep.iter().map(|&i| column_names[i].clone()).collect();
+            (select_columns(&tensor, &keep)?, names)
+        }
+        None => (tensor, column_names),
+    };
+    let kept_indices: Vec<usize> = kept.iter()
+        .filter_map(|t| column_names.iter().position(|n| n == t))
         .collect();
 
+    // Bin features exactly like the backend's mRMR and SURD do
+    let tensor = match parse_discretizer(n_bins, discretization.as_deref())? {
+        Some(discretizer) => discretizer.bin_tensor_keeping(&tensor, &kept_indices)
+            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?,
+        None => tensor,
+    };
+
+    // Same selector as the CLI: upstream unless an option needs the native one. A trace
+    // always needs the native selector, which also reports progress (and polls Ctrl+C)
+    // after every round; the upstream one runs each target in a single step on a worker
+    // thread, with a heartbeat report while it runs
+    let tensor = Arc::new(tensor);
+    let column_names = Arc::new(column_names);
+    let mut options = MrmrOptions::new(max_features).with_condition_on(condition_on);
+    if let Some(n_threads) = n_threads {
+        options = options.with_threads(n_threads);
+    }
+    if let Some(weights) = sample_weights {
+        options = options.with_sample_weights(weights);
+    }
+    let n_targets = targets.len() as f64;
+    let runs = with_python_progress(&progress_callback, |on_progress| {
+        if trace || options.needs_native_selector() {
+            return CausalDiscovery::run_mrmr_tensor_multi_target(&tensor, &column_names, &targets, &options, on_progress);
+        }
+        targets.iter()
+            .enumerate()
+            .map(|(t, target)| {
+                on_progress(0, 100.0 * t as f64 / n_targets)?;
+                let others = targets.iter().filter(|o| *o != target).cloned().collect();
+                let target_options = options.clone().with_exclude_columns(others);
+                let (tensor, column_names, target_name) = (Arc::clone(&tensor), Arc::clone(&column_names), target.clone());
+                let selected = with_heartbeat(on_progress, 0, 100.0 * t as f64 / n_targets, move || {
+                    CausalDiscovery::run_mrmr_tensor_described(&tensor, &column_names, &target_name, &target_options)
+                })?;
+                on_progress(selected.len(), 100.0 * (t + 1) as f64 / n_targets)?;
+                // One step per selected feature, holding only that feature's scores
+                let steps = selected.into_iter()
+                    .enumerate()
+                    .map(|(i, chosen)| icu_sepsis_backend::causality::SelectionStep {
+                        round: i + 1,
+                        selected: chosen.name.clone(),
+                        candidates: vec![chosen],
+                    })
+                    .collect();
+                Ok((target.clone(), steps))
+            })
+            .collect()
+    })?;
+
+    let mut results: Vec<(String, MrmrFullResult)> = runs.into_iter()
+        .map(|(target, steps)| {
+            let result = rankings_from_steps(steps, min_score, normalize_scores, &target)?;
+            Ok((target, result))
+        })
+        .collect::<PyResult<_>>()?;
+
+    if let Some(n_resamples) = bootstrap {
+        for (target, result) in &mut results {
+            // Resample the same prepared tensor; other targets stay out of the pool as above
+            let others = targets.iter().filter(|t| *t != target).cloned().collect();
+            let mut boot_options = options.clone().with_exclude_columns(others);
+            if let Some(min) = min_score {
+                boot_options = boot_options.with_min_score(min);
+            }
+            let scores = CausalDiscovery::bootstrap_mrmr(
+                &tensor, &column_names, target, &boot_options, n_resamples, random_seed,
+            ).map_err(backend_err)?;
+            // Divide by the same top score as normalized_score, so the two are comparable
+            let top = result.features.iter().map(|r| r.score).fold(f64::NEG_INFINITY, f64::max);
+            let scale = if normalize_scores && top > 0.0 && top.is_finite() { top } else { 1.0 };
+            for ranking in &mut result.features {
+                let stats = scores.iter().find(|s| s.name == ranking.name);
+                ranking.bootstrap_mean = stats.map(|s| s.mean_score / scale);
+                ranking.bootstrap_std = stats.map(|s| s.std_score / scale);
+                ranking.selection_frequency = Some(stats.map_or(0.0, |s| s.selection_frequency));
+            }
+        }
+    }
     Ok(results)
 }
 
+/// Run a selection whose progress hook re-acquires the GIL at every report, so Ctrl+C
+/// aborts it and `progress_callback` is called; an error raised there is re-raised
+fn with_python_progress<T>(
+    progress_callback: &Option<PyObject>,
+    run: impl FnOnce(ProgressHook) -> std::result::Result<T, BackendError>,
+) -> PyResult<T> {
+    let mut python_err: Option<PyErr> = None;
+    let result = run(&mut |selected, percent| {
+        Python::with_gil(|py| {
+            py.check_signals()?;
+            if let Some(callback) = progress_callback {
+                callback.call1(py, (selected, percent))?;
+            }
+            Ok(())
+        })
+        .map_err(|e: PyErr| {
+            python_err = Some(e);
+            BackendError::Mrmr("interrupted from Python".into())
+        })
+    });
+    match python_err {
+        Some(e) => Err(e),
+        None => result.map_err(backend_err),
+    }
+}
+
+/// How often a selection running on a worker thread reports progress
+const HEARTBEAT: Duration = Duration::from_millis(250);
+
+/// Run `work` on a worker thread, repeating the report (selected, percent) every
+/// `HEARTBEAT` until it finishes, so Ctrl+C and the progress callback stay live during a
+/// single long step. If the hook fails the error returns at once; the worker is left to
+/// finish in the background and its result is dropped.
+fn with_heartbeat<T: Send + 'static>(
+    on_progress: ProgressHook,
+    selected: usize,
+    percent: f64,
+    work: impl FnOnce() -> std::result::Result<T, BackendError> + Send + 'static,
+) -> std::result::Result<T, BackendError> {
+    let (sender, receiver) = mpsc::channel();
+    std::thread::spawn(move || {
+        // The receiver is gone when the run was interrupted
+        let _ = sender.send(work());
+    });
+    loop {
+        match receiver.recv_timeout(HEARTBEAT) {
+            Ok(result) => return result,
+            Err(RecvTimeoutError::Timeout) => on_progress(selected, percent)?,
+            Err(RecvTimeoutError::Disconnected) => {
+                return Err(BackendError::Mrmr("mRMR worker thread panicked".into()));
+            }
+        }
+    }
+}
+
+/// Selected features from a traced run, cut at `min_score` and optionally normalized
+fn rankings_from_steps(
+    steps: Vec<icu_sepsis_backend::causality::SelectionStep>,
+    min_score: Option<f64>,
+    normalize_scores: bool,
+    target: &str,
+) -> PyResult<MrmrFullResult> {
+    let mut steps: Vec<SelectionStep> = steps.into_iter().map(SelectionStep::from).collect();
+
+    // Cut everything after the first score below min_score
+    let kept = steps.iter()
+        .filter_map(SelectionStep::chosen)
+        .take_while(|chosen| min_score.map_or(true, |min| chosen.score >= min))
+        .count();
+    steps.truncate(kept);
+
+    let mut results: Vec<FeatureRanking> = steps.iter()
+        .filter_map(SelectionStep::chosen)
+        .enumerate()
+        .map(|(i, chosen)| FeatureRanking {
+            name: chosen.name.clone(),
+            rank: i + 1,
+            score: chosen.score,
+            normalized_score: None,
+            relevance: Some(chosen.relevance),
+            redundancy: Some(chosen.redundancy),
+            bootstrap_mean: None,
+            bootstrap_std: None,
+            selection_frequency: None,
+        })
+        .collect();
+
+    if results.is_empty() {
+        return Err(MrmrError::new_err(
+            BackendError::NoFeaturesSelected(target.to_string()).to_string()
+        ));
+    }
+
+    // Raw scores stay in `score`; a non-positive top score has no scale to normalize against
+    let top = results.iter().map(|r| r.score).fold(f64::NEG_INFINITY, f64::max);
+    if normalize_scores && top > 0.0 && top.is_finite() {
+        for ranking in &mut results {
+            ranking.normalized_score = Some(ranking.score / top);
+        }
+    }
+
+    Ok(MrmrFullResult { features: results, steps })
+}
+
 /// Run mRMR on a Polars DataFrame (passed as dict of columns)
 ///
 /// Args:
 ///     df_dict: Dictionary mapping column names to lists of values
-///     target_column: Name of the target column
+///     target_column: Name of the target column, or a list of them as in `run_mrmr`
 ///     max_features: Maximum number of features to select
+///     min_score: Stop selecting once the next feature scores below this cutoff
+///     include_columns: Only consider these columns as candidates
+///     exclude_columns: Never select these columns (cannot be combined with include_columns)
+///     normalize_scores: Also report each score relative to the top feature (0..1)
+///     n_bins: Number of bins when discretization is set (default: 10)
+///     discretization: "equal_width", "equal_frequency" or "kmeans" to bin features first, as the backend does
+///     progress_callback: Called as callback(features_selected, percent_complete), as in `run_mrmr`
+///     n_threads: Threads for candidate scoring, as in `run_mrmr`
+///     bootstrap: Number of row resamples for selection-stability statistics, as in `run_mrmr`
+///     random_seed: Seed for the bootstrap resamples
+///     sample_weights: One weight per row, as in `run_mrmr`
 ///
 /// Returns:
-///     List of FeatureRanking objects
+///     List of FeatureRanking objects, or a dict of them per target as in `run_mrmr`
+#[allow(clippy::too_many_arguments)]
 #[pyfunction]
-#[pyo3(signature = (df_dict, target_column, max_features=10))]
+#[pyo3(signature = (df_dict, target_column, max_features=10, min_score=None, include_columns=None, exclude_columns=None, normalize_scores=false, n_bins=10, discretization=None, progress_callback=None, n_threads=None, bootstrap=None, random_seed=None, sample_weights=None))]
 fn run_mrmr_from_dict(
     py: Python,
     df_dict: &PyDict,
-    target_column: String,
+    target_column: Targets,
     max_features: usize,
-) -> PyResult<Vec<FeatureRanking>> {
+    min_score: Option<f64>,
+    include_columns: Option<Vec<String>>,
+    exclude_columns: Option<Vec<String>>,
+    normalize_scores: bool,
+    n_bins: usize,
+    discretization: Option<String>,
+    progress_callback: Option<PyObject>,
+    n_threads: Option<usize>,
+    bootstrap: Option<usize>,
+    random_seed: Option<u64>,
+    sample_weights: Option<Vec<f64>>,
+) -> PyResult<PyObject> {
+    // Report every non-numeric column at once instead of failing on the first value
+    let mut unsupported: Vec<String> = Vec::new();
+    for (key, values) in df_dict.iter() {
+        if let Some(found) = first_non_numeric(values)? {
+            unsupported.push(format!("'{}' ({})", key, found));
+        }
+    }
+    if !unsupported.is_empty() {
+        return Err(ColumnTypeError::new_err(format!(
+            "Columns with non-numeric values: {}. Convert them to numbers, drop them, or encode \
+             them with encode_columns()",
+            unsupported.join(", ")
+        )));
+    }
+
     let mut column_names: Vec<String> = Vec::new();
-    let mut data: Vec<Vec<f64>> = Vec::new();
+    let mut data: Vec<Vec<Option<f64>>> = Vec::new();
     let mut n_rows: Option<usize> = None;
 
     // Extract columns from dict
     for (key, value) in df_dict.iter() {
         let col_name: String = key.extract()?;
-        let col_data: Vec<f64> = value.extract()?;
+        let col_data: Vec<Option<f64>> = value.extract()?;
         
         if let Some(expected_rows) = n_rows {
             if col_data.len() != expected_rows {
-                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                return Err(TensorShapeError::new_err(
                     "All columns must have the same length"
                 ));
             }
@@ -171,7 +1039,7 @@ fn run_mrmr_from_dict(
     // Transpose: from column-oriented to row-oriented
     let n_rows = n_rows.unwrap_or(0);
     let n_cols = column_names.len();
-    let mut row_data: Vec<Vec<f64>> = vec![vec![0.0; n_cols]; n_rows];
+    let mut row_data: Vec<Vec<Option<f64>>> = vec![vec![None; n_cols]; n_rows];
     
     for (col_idx, col) in data.iter().enumerate() {
         for (row_idx, &val) in col.iter().enumerate() {
@@ -179,22 +1047,427 @@ fn run_mrmr_from_dict(
         }
     }
 
-    run_mrmr(row_data, column_names, target_column, max_features)
+    run_mrmr(
+        py, row_data, column_names, target_column, max_features, min_score,
+        include_columns, exclude_columns, normalize_scores, n_bins, discretization, progress_callback, n_threads,
+        bootstrap, random_seed, sample_weights,
+    )
+}
+
+/// The first value in a column that isn't a number or None, described as "type, row i: value"
+fn first_non_numeric(values: &PyAny) -> PyResult<Option<String>> {
+    for (row, value) in values.iter()?.enumerate() {
+        let value = value?;
+        if !value.is_none() && value.extract::<f64>().is_err() {
+            return Ok(Some(format!("{}, row {}: {}", value.get_type().name()?, row, value.repr()?)));
+        }
+    }
+    Ok(None)
+}
+
+/// Encode columns holding strings (or other non-numeric values) as integer codes
+///
+/// Codes follow the sorted string form of the distinct values, so the same
+/// categories always get the same codes; None stays missing. Numeric columns
+/// (booleans read as 0/1) pass through unchanged.
+///
+/// Args:
+///     df_dict: Dictionary mapping column names to lists of values
+///
+/// Returns:
+///     (encoded dict ready for run_mrmr_from_dict, {column: {value: code}} for encoded columns)
+#[pyfunction]
+fn encode_columns(py: Python, df_dict: &PyDict) -> PyResult<(PyObject, PyObject)> {
+    let encoded = PyDict::new(py);
+    let encodings = PyDict::new(py);
+    for (key, values) in df_dict.iter() {
+        if first_non_numeric(values)?.is_none() {
+            let column: Vec<Option<f64>> = values.extract()?;
+            encoded.set_item(key, column)?;
+            continue;
+        }
+
+        let labels: Vec<Option<String>> = values.iter()?
+            .map(|value| {
+                let value = value?;
+                if value.is_none() { Ok(None) } else { Ok(Some(value.str()?.to_string())) }
+            })
+            .collect::<PyResult<_>>()?;
+        let categories: BTreeMap<&str, usize> = labels.iter()
+            .flatten()
+            .map(String::as_str)
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .enumerate()
+            .map(|(code, label)| (label, code))
+            .collect();
+
+        let column: Vec<Option<f64>> = labels.iter()
+            .map(|label| label.as_deref().map(|l| categories[l] as f64))
+            .collect();
+        encoded.set_item(key, column)?;
+        encodings.set_item(key, categories.into_iter().collect::<HashMap<_, _>>())?;
+    }
+    Ok((encoded.into(), encodings.into()))
+}
+
+/// Column name -> profile dict, in column order
+fn profiles_to_dict(py: Python, profiles: Vec<ColumnProfile>) -> PyResult<PyObject> {
+    let report = PyDict::new(py);
+    for profile in profiles {
+        let column = PyDict::new(py);
+        column.set_item("count", profile.count)?;
+        column.set_item("missing", profile.missing)?;
+        column.set_item("missing_fraction", profile.missing_fraction)?;
+        column.set_item("n_unique", profile.n_unique)?;
+        column.set_item("constant", profile.constant)?;
+        column.set_item("mean", profile.mean)?;
+        column.set_item("std", profile.std)?;
+        column.set_item("min", profile.min)?;
+        column.set_item("max", profile.max)?;
+        column.set_item("paired_with_target", profile.paired_with_target)?;
+        column.set_item("issues", profile.issues)?;
+        report.set_item(profile.name, column)?;
+    }
+    Ok(report.into())
+}
+
+/// Data-quality report, to see why mRMR skips or zero-scores columns
+///
+/// Args:
+///     data: 2D list of floats (rows x columns); None or NaN marks a missing value
+///     column_names: List of column names
+///     target_column: Also count each column's rows that overlap this target
+///
+/// Returns:
+///     Dict mapping each column to {"count", "missing", "missing_fraction", "n_unique",
+///     "constant", "mean", "std", "min", "max", "paired_with_target", "issues"}; statistics
+///     of an all-missing column are None, as is "paired_with_target" without a target.
+///     "issues" lists problems in plain words, empty for a usable column.
+#[pyfunction]
+#[pyo3(signature = (data, column_names, target_column=None))]
+fn profile_data(py: Python, data: Vec<Vec<Option<f64>>>, column_names: Vec<String>, target_column: Option<String>) -> PyResult<PyObject> {
+    let profiles = py.allow_threads(|| {
+        let (tensor, _, _) = py_data_to_tensor(data)?;
+        profile_tensor(&tensor, &column_names, target_column.as_deref()).map_err(backend_err)
+    })?;
+    profiles_to_dict(py, profiles)
+}
+
+/// Score how much each feature's past tells about the target (transfer entropy)
+///
+/// Rows are read as consecutive time steps; with `group_column` (e.g. a patient id)
+/// they are split into separate series wherever its value changes, so lags never
+/// reach into another patient's stay. Every column is binned before counting states.
+///
+/// Args:
+///     data: 2D list of floats (rows x columns) in time order; None or NaN marks a missing value
+///     column_names: List of column names
+///     target_column: Name of the target column
+///     max_lag: Score lags 1..max_lag, in rows (default: 5)
+///     group_column: Column identifying independent series; never scored itself
+///     n_bins: Number of bins per column (default: 10)
+///     discretization: "equal_width", "equal_frequency" (default) or "kmeans"
+///
+/// Returns:
+///     List of TransferEntropyScore objects, one per feature and lag, ordered by column then lag.
+///     Plug-in estimates run high on few samples per state, so compare them rather
+///     than reading them as absolute values.
+#[pyfunction]
+#[pyo3(signature = (data, column_names, target_column, max_lag=5, group_column=None, n_bins=10, discretization="equal_frequency"))]
+fn run_transfer_entropy(
+    py: Python,
+    data: Vec<Vec<Option<f64>>>,
+    column_names: Vec<String>,
+    target_column: String,
+    max_lag: usize,
+    group_column: Option<String>,
+    n_bins: usize,
+    discretization: &str,
+) -> PyResult<Vec<TransferEntropyScore>> {
+    let strategy: DiscretizationStrategy = discretization.parse()
+        .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?;
+    if max_lag == 0 {
+        return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>("max_lag must be at least 1"));
+    }
+    if let Some(missing) = std::iter::once(&target_column).chain(&group_column).find(|c| !column_names.contains(*c)) {
+        return Err(MissingColumnError::new_err(format!("Column '{}' not found", missing)));
+    }
+
+    let mut options = TransferEntropyOptions::new(max_lag).with_discretizer(Discretizer::new(n_bins, strategy));
+    if let Some(group_column) = group_column {
+        options = options.with_group_column(group_column);
+    }
+    py.allow_threads(|| {
+        let (tensor, _, _) = py_data_to_tensor(data)?;
+        let scores = CausalDiscovery::transfer_entropy(&tensor, &column_names, &target_column, &options)
+            .map_err(backend_err)?;
+        Ok(scores.into_iter()
+            .map(|s| TransferEntropyScore {
+                feature: s.feature,
+                lag: s.lag,
+                transfer_entropy: s.transfer_entropy,
+                n_samples: s.n_samples,
+            })
+            .collect())
+    })
+}
+
+/// Mutual information between every pair of columns, for dependence heatmaps
+///
+/// Uses the pairwise Pearson correlations mRMR scores relevance and redundancy with,
+/// as Gaussian mutual information -log2(1 - r^2) / 2 in bits: exact for jointly normal
+/// columns, a lower bound otherwise. Each pair uses the rows where both are present.
+///
+/// Args:
+///     data: 2D list of floats (rows x columns); None or NaN marks a missing value
+///     column_names: List of column names
+///     n_bins: Number of bins when discretization is set (default: 10)
+///     discretization: "equal_width", "equal_frequency" or "kmeans" to bin every column first, as in `run_mrmr`
+///     n_threads: Compute pairs on this many threads (0 = one per core)
+///
+/// Returns:
+///     N x N nested list in `column_names` order; symmetric, with NaN on the diagonal
+#[pyfunction]
+#[pyo3(signature = (data, column_names, n_bins=10, discretization=None, n_threads=None))]
+fn pairwise_information_matrix(
+    py: Python,
+    data: Vec<Vec<Option<f64>>>,
+    column_names: Vec<String>,
+    n_bins: usize,
+    discretization: Option<String>,
+    n_threads: Option<usize>,
+) -> PyResult<Vec<Vec<f64>>> {
+    let discretizer = parse_discretizer(n_bins, discretization.as_deref())?;
+    py.allow_threads(|| {
+        let (tensor, _, n_cols) = py_data_to_tensor(data)?;
+        if column_names.len() != n_cols {
+            return Err(TensorShapeError::new_err(format!(
+                "Got {} column names but data has {} columns", column_names.len(), n_cols
+            )));
+        }
+        let tensor = match discretizer {
+            Some(discretizer) => discretizer.bin_tensor_keeping(&tensor, &[])
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?,
+            None => tensor,
+        };
+        CausalDiscovery::information_matrix(&tensor, n_threads).map_err(backend_err)
+    })
+}
+
+/// Run SURD (Synergistic Unique Redundant Decomposition) of the target's information
+///
+/// Args:
+///     data: 2D list of floats (rows x columns); None or NaN marks a missing value
+///     column_names: List of column names
+///     target_column: Name of the target column; every other column is a source
+///     n_bins: Number of bins when discretization is set (default: 10)
+///     discretization: "equal_width", "equal_frequency" or "kmeans" to bin features first
+///     progress_callback: Called as callback(features_done, percent_complete) when the
+///         decomposition starts and finishes (it runs as a single step)
+///     sample_weights: One non-negative weight per row. SURD counts rows, so each row is
+///         repeated in proportion to its weight; weights in integer ratios are exact
+///
+/// Returns:
+///     SurdResult with redundant, unique, synergistic and total information
+#[allow(clippy::too_many_arguments)]
+#[pyfunction]
+#[pyo3(signature = (data, column_names, target_column, n_bins=10, discretization=None, progress_callback=None, sample_weights=None))]
+fn run_surd(
+    py: Python,
+    data: Vec<Vec<Option<f64>>>,
+    column_names: Vec<String>,
+    target_column: String,
+    n_bins: usize,
+    discretization: Option<String>,
+    progress_callback: Option<PyObject>,
+    sample_weights: Option<Vec<f64>>,
+) -> PyResult<SurdResult> {
+    let discretizer = parse_discretizer(n_bins, discretization.as_deref())?;
+    if !column_names.contains(&target_column) {
+        return Err(MissingColumnError::new_err(format!("Target column '{}' not found", target_column)));
+    }
+
+    if let Some(callback) = &progress_callback {
+        callback.call1(py, (0, 0.0))?;
+    }
+
+    let result = py.allow_threads(|| {
+        let (mut tensor, _, _) = py_data_to_tensor(data)?;
+        if let Some(weights) = &sample_weights {
+            tensor = TensorAdapter::repeat_rows_by_weight(&tensor, weights).map_err(backend_err)?;
+        }
+        surd_on_tensor(&tensor, &column_names, &target_column, discretizer)
+    })?;
+
+    if let Some(callback) = &progress_callback {
+        callback.call1(py, (column_names.len() - 1, 100.0))?;
+    }
+    Ok(result)
+}
+
+/// Shared SURD path once the input is a column-major tensor; every non-target column is a source
+fn surd_on_tensor(
+    tensor: &CausalTensor<Option<f64>>,
+    column_names: &[String],
+    target_column: &str,
+    discretizer: Option<Discretizer>,
+) -> PyResult<SurdResult> {
+    let target_idx = column_names.iter()
+        .position(|n| n == target_column)
+        .ok_or_else(|| MissingColumnError::new_err(
+            format!("Target column '{}' not found", target_column)
+        ))?;
+    let n_cols = tensor.shape()[1];
+    if column_names.len() != n_cols {
+        return Err(TensorShapeError::new_err(format!(
+            "Got {} column names but data has {} columns",
+            column_names.len(), n_cols
+        )));
+    }
+
+    let binned;
+    let tensor = match discretizer {
+        Some(discretizer) => {
+            binned = discretizer.bin_tensor(tensor, target_idx)
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string()))?;
+            &binned
+        }
+        None => tensor,
+    };
+
+    let agent_indices: Vec<usize> = (0..n_cols).filter(|&i| i != target_idx).collect();
+    let result = surd_states(tensor, target_idx, &agent_indices)
+        .map_err(|e| SurdError::new_err(format!("{:?}", e)))?;
+
+    // Same totals as the backend's SURD report
+    let (redundant, unique, synergistic) = CausalDiscovery::aggregate_surd_result(&result);
+    Ok(SurdResult {
+        redundant_info: redundant,
+        unique_info: unique,
+        synergistic_info: synergistic,
+        total_info: redundant + unique + synergistic,
+        per_feature: per_feature_dicts(&CausalDiscovery::surd_by_feature(&result, column_names, &agent_indices)),
+    })
+}
+
+/// Compare SURD decompositions of the Sepsis and Non-Sepsis subsets, as the CLI does
+///
+/// Args:
+///     sepsis_data: 2D list of floats (rows x columns) for sepsis patients
+///     non_sepsis_data: 2D list of floats with the same columns for non-sepsis patients
+///     column_names: List of column names shared by both datasets
+///     target: Name of the target column
+///     n_bins: Number of bins when discretization is set (default: 10)
+///     discretization: "equal_width", "equal_frequency" or "kmeans" to bin features first
+///
+/// Returns:
+///     SurdDualResult with both decompositions, disjoint and shared drivers, and specificity score
+#[pyfunction]
+#[pyo3(signature = (sepsis_data, non_sepsis_data, column_names, target, n_bins=10, discretization=None))]
+fn run_surd_dual(
+    py: Python,
+    sepsis_data: Vec<Vec<Option<f64>>>,
+    non_sepsis_data: Vec<Vec<Option<f64>>>,
+    column_names: Vec<String>,
+    target: String,
+    n_bins: usize,
+    discretization: Option<String>,
+) -> PyResult<SurdDualResult> {
+    let mut options = SurdOptions::new();
+    if let Some(discretizer) = parse_discretizer(n_bins, discretization.as_deref())? {
+        options = options.with_discretizer(discretizer);
+    }
+    if !column_names.contains(&target) {
+        return Err(MissingColumnError::new_err(format!("Target column '{}' not found", target)));
+    }
+
+    py.allow_threads(|| {
+        let sepsis_df = py_data_to_frame(&sepsis_data, &column_names)?;
+        let non_sepsis_df = py_data_to_frame(&non_sepsis_data, &column_names)?;
+        let dual = CausalDiscovery::run_surd_dual(&sepsis_df, &non_sepsis_df, &target, &options)
+            .map_err(backend_err)?;
+
+        Ok(SurdDualResult {
+            sepsis_result: dual.sepsis_result.into(),
+            non_sepsis_result: dual.non_sepsis_result.into(),
+            disjoint_drivers: dual.disjoint_drivers,
+            shared_drivers: dual.shared_drivers,
+            sepsis_specific_score: dual.sepsis_specific_score,
+        })
+    })
 }
 
 /// Get library version
 #[pyfunction]
 fn version() -> &'static str {
-    "0.1.0"
+    env!("CARGO_PKG_VERSION")
+}
+
+/// Build metadata for reproducibility logs
+///
+/// Returns:
+///     Dict with "version", "git_hash" (of the source tree the extension was built from,
+///     "unknown" outside a git checkout), "profile" ("release" or "debug"), "features"
+///     (enabled Cargo features of the extension) and "deep_causality", mapping each
+///     linked deep_causality crate to {"version", "git_rev"}
+#[pyfunction]
+fn build_info(py: Python) -> PyResult<PyObject> {
+    let info = PyDict::new(py);
+    info.set_item("version", env!("CARGO_PKG_VERSION"))?;
+    info.set_item("git_hash", env!("DEEP_CAUSALITY_PY_GIT_HASH"))?;
+    info.set_item("profile", env!("DEEP_CAUSALITY_PY_PROFILE"))?;
+    let features: Vec<&str> = env!("DEEP_CAUSALITY_PY_FEATURES").split(',').filter(|f| !f.is_empty()).collect();
+    info.set_item("features", features)?;
+
+    let linked = PyDict::new(py);
+    for entry in env!("DEEP_CAUSALITY_PY_LINKED").split(';').filter(|e| !e.is_empty()) {
+        let Some((name, pinned)) = entry.split_once('=') else { continue };
+        let (crate_version, git_rev) = pinned.split_once('@').unwrap_or((pinned, ""));
+        let details = PyDict::new(py);
+        details.set_item("version", crate_version)?;
+        details.set_item("git_rev", (!git_rev.is_empty()).then_some(git_rev))?;
+        linked.set_item(name, details)?;
+    }
+    info.set_item("deep_causality", linked)?;
+    Ok(info.into())
 }
 
 /// Main Python module
 #[pymodule]
-fn _core(_py: Python, m: &PyModule) -> PyResult<()> {
+fn _core(py: Python, m: &PyModule) -> PyResult<()> {
+    m.add("DeepCausalityError", py.get_type::<DeepCausalityError>())?;
+    m.add("MrmrError", py.get_type::<MrmrError>())?;
+    m.add("SurdError", py.get_type::<SurdError>())?;
+    m.add("TensorShapeError", py.get_type::<TensorShapeError>())?;
+    m.add("MissingColumnError", py.get_type::<MissingColumnError>())?;
+    m.add("ColumnTypeError", py.get_type::<ColumnTypeError>())?;
     m.add_class::<FeatureRanking>()?;
+    m.add_class::<CandidateScore>()?;
+    m.add_class::<SelectionStep>()?;
+    m.add_class::<MrmrFullResult>()?;
     m.add_class::<SurdResult>()?;
+    m.add_class::<SurdDualResult>()?;
+    m.add_class::<TransferEntropyScore>()?;
     m.add_function(wrap_pyfunction!(run_mrmr, m)?)?;
+    m.add_function(wrap_pyfunction!(run_mrmr_full, m)?)?;
+    m.add_function(wrap_pyfunction!(run_mrmr_conditional, m)?)?;
     m.add_function(wrap_pyfunction!(run_mrmr_from_dict, m)?)?;
+    m.add_function(wrap_pyfunction!(encode_columns, m)?)?;
+    m.add_function(wrap_pyfunction!(run_mrmr_numpy, m)?)?;
+    m.add_function(wrap_pyfunction!(run_mrmr_polars, m)?)?;
+    m.add_function(wrap_pyfunction!(run_surd, m)?)?;
+    m.add_function(wrap_pyfunction!(run_surd_dual, m)?)?;
+    m.add_function(wrap_pyfunction!(run_transfer_entropy, m)?)?;
+    m.add_function(wrap_pyfunction!(pairwise_information_matrix, m)?)?;
+    m.add_function(wrap_pyfunction!(profile_data, m)?)?;
     m.add_function(wrap_pyfunction!(version, m)?)?;
+    m.add_function(wrap_pyfunction!(build_info, m)?)?;
+    dataset::register(m)?;
+    ethos::register(m)?;
+    graph::register(m)?;
+    pipeline::register(m)?;
+    selector::register(m)?;
+    streaming::register(m)?;
     Ok(())
 }
@@ -0,0 +1,213 @@
+//! One-call feature selection → SURD → graph pipeline
+//!
+//! The data is converted once when the pipeline is built, so notebooks can rerun
+//! it with different settings without passing the data in again.
+
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use polars::prelude::*;
+use icu_sepsis_backend::causality::{CausalDiscovery, DiscretizationStrategy, Discretizer, MrmrOptions, SurdOptions};
+use icu_sepsis_backend::visualization::CausalGraph;
+
+use serde::{Serialize, Deserialize};
+
+use crate::serialization::impl_serialization;
+use crate::{backend_err, py_data_to_frame, FeatureRanking, MissingColumnError, SurdResult};
+
+/// Pipeline settings, read from a Python config dict
+#[derive(Clone)]
+struct PipelineConfig {
+    max_features: usize,
+    min_score: Option<f64>,
+    normalize_scores: bool,
+    discretizer: Option<Discretizer>,
+    surd: bool,
+}
+
+impl Default for PipelineConfig {
+    fn default() -> Self {
+        Self {
+            max_features: 10,
+            min_score: None,
+            normalize_scores: false,
+            discretizer: None,
+            surd: true,
+        }
+    }
+}
+
+impl PipelineConfig {
+    /// Unknown keys are rejected so a typo doesn't silently fall back to a default
+    fn from_dict(config: &PyDict) -> PyResult<Self> {
+        Self::default().with_overrides(config)
+    }
+
+    /// This config with the keys present in `config` replaced
+    fn with_overrides(&self, config: &PyDict) -> PyResult<Self> {
+        let mut parsed = self.clone();
+        let mut n_bins = self.discretizer.map_or(10, |d| d.n_bins);
+        let mut strategy: Option<DiscretizationStrategy> = self.discretizer.map(|d| d.strategy);
+
+        for (key, value) in config.iter() {
+            match key.extract::<&str>()? {
+                "max_features" => parsed.max_features = value.extract()?,
+                "min_score" => parsed.min_score = value.extract()?,
+                "normalize_scores" => parsed.normalize_scores = value.extract()?,
+                "n_bins" => n_bins = value.extract()?,
+                "discretization" => {
+                    strategy = value.extract::<Option<&str>>()?
+                        .map(str::parse::<DiscretizationStrategy>)
+                        .transpose()
+                        .map_err(PyErr::new::<pyo3::exceptions::PyValueError, _>)?;
+                }
+                "surd" => parsed.surd = value.extract()?,
+                other => {
+                    return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                        "Unknown pipeline config key '{}'", other
+                    )));
+                }
+            }
+        }
+
+        parsed.discretizer = strategy.map(|s| Discretizer::new(n_bins, s));
+        Ok(parsed)
+    }
+}
+
+/// Everything a pipeline run produced
+#[pyclass(module = "deep_causality._core")]
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PipelineResult {
+    /// Selected features, best first
+    #[pyo3(get)]
+    features: Vec<FeatureRanking>,
+    /// SURD decomposition of the target over the selected features (None when disabled)
+    #[pyo3(get)]
+    surd: Option<SurdResult>,
+    /// Feature graph in Graphviz DOT format
+    #[pyo3(get)]
+    graph_dot: String,
+    /// Feature graph as JSON for web visualization
+    #[pyo3(get)]
+    graph_json: String,
+}
+
+#[pymethods]
+impl PipelineResult {
+    fn __repr__(&self) -> String {
+        format!(
+            "PipelineResult(features={}, surd={})",
+            self.features.len(),
+            if self.surd.is_some() { "yes" } else { "no" }
+        )
+    }
+}
+
+impl_serialization!(PipelineResult);
+
+/// Feature selection, SURD and graph construction over one dataset
+///
+/// Config keys: max_features (10), min_score, normalize_scores (False),
+/// n_bins (10), discretization ("equal_width"/"equal_frequency"/"kmeans"), surd (True).
+#[pyclass]
+pub struct CausalPipeline {
+    df: DataFrame,
+    target: String,
+    config: PipelineConfig,
+}
+
+#[pymethods]
+impl CausalPipeline {
+    #[new]
+    #[pyo3(signature = (data, column_names, target, config=None))]
+    fn new(
+        data: Vec<Vec<Option<f64>>>,
+        column_names: Vec<String>,
+        target: String,
+        config: Option<&PyDict>,
+    ) -> PyResult<Self> {
+        if !column_names.contains(&target) {
+            return Err(MissingColumnError::new_err(format!("Target column '{}' not found", target)));
+        }
+        let config = config.map(PipelineConfig::from_dict).transpose()?.unwrap_or_default();
+        let df = py_data_to_frame(&data, &column_names)?;
+        Ok(Self { df, target, config })
+    }
+
+    /// Run every stage; keys in `config` override the settings given at construction for this run
+    #[pyo3(signature = (config=None))]
+    fn run(&self, py: Python, config: Option<&PyDict>) -> PyResult<PipelineResult> {
+        let config = match config {
+            Some(config) => self.config.with_overrides(config)?,
+            None => self.config.clone(),
+        };
+        py.allow_threads(|| self.run_stages(&config))
+    }
+}
+
+impl CausalPipeline {
+    fn run_stages(&self, config: &PipelineConfig) -> PyResult<PipelineResult> {
+        let target = self.target.as_str();
+
+        // 1. Feature selection
+        let mut options = MrmrOptions::new(config.max_features);
+        if let Some(discretizer) = config.discretizer {
+            options = options.with_discretizer(discretizer);
+        }
+        if let Some(min_score) = config.min_score {
+            options = options.with_min_score(min_score);
+        }
+        let selected = CausalDiscovery::run_mrmr(&self.df, target, &options).map_err(backend_err)?;
+        CausalDiscovery::ensure_selected(&selected, target).map_err(backend_err)?;
+        let normalized = config.normalize_scores.then(|| CausalDiscovery::normalize_scores(&selected));
+
+        let features = selected.iter()
+            .enumerate()
+            .map(|(i, (name, score))| FeatureRanking {
+                name: name.clone(),
+                rank: i + 1,
+                score: *score,
+                normalized_score: normalized.as_ref().map(|n| n[i].1),
+                // The backend's selector only reports combined scores
+                relevance: None,
+                redundancy: None,
+                bootstrap_mean: None,
+                bootstrap_std: None,
+                selection_frequency: None,
+            })
+            .collect();
+
+        // 2. SURD over the selected features only
+        let surd = if config.surd {
+            let mut columns: Vec<&str> = selected.iter().map(|(name, _)| name.as_str()).collect();
+            columns.push(target);
+            let subset = self.df.select(columns).map_err(|e| backend_err(e.into()))?;
+
+            let mut surd_options = SurdOptions::new();
+            if let Some(discretizer) = config.discretizer {
+                surd_options = surd_options.with_discretizer(discretizer);
+            }
+            let result = CausalDiscovery::run_surd(&subset, target, &surd_options).map_err(backend_err)?;
+            Some(result.into())
+        } else {
+            None
+        };
+
+        // 3. Graph, weighted like the CLI's export
+        let graph = CausalGraph::from_mrmr_results(normalized.as_ref().unwrap_or(&selected), target);
+        let graph_json = graph.to_json().map_err(backend_err)?;
+
+        Ok(PipelineResult {
+            features,
+            surd,
+            graph_dot: graph.to_dot(),
+            graph_json,
+        })
+    }
+}
+
+pub fn register(m: &PyModule) -> PyResult<()> {
+    m.add_class::<PipelineResult>()?;
+    m.add_class::<CausalPipeline>()?;
+    Ok(())
+}
@@ -0,0 +1,327 @@
+//! mRMR as a scikit-learn feature selector
+//!
+//! `MrmrSelector` follows the estimator contract scikit-learn relies on: constructor
+//! arguments are stored untouched and only read in `fit`, `get_params`/`set_params`
+//! round-trip them for `clone`, and fitted state lives in trailing-underscore
+//! attributes. That is enough for Pipelines, grid search and cross-validation.
+
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PySlice, PyTuple};
+use numpy::PyReadonlyArray2;
+use serde::{Serialize, Deserialize};
+
+use crate::{encode_columns, mrmr_on_tensor, numpy_to_tensor, serialization, FeatureRanking};
+
+const PARAMS: [&str; 6] = ["max_features", "min_score", "normalize_scores", "n_bins", "discretization", "n_threads"];
+
+fn value_err(message: impl Into<String>) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyValueError, _>(message.into())
+}
+
+/// What `fit` learned
+#[derive(Clone, Serialize, Deserialize)]
+struct Fitted {
+    /// One flag per input column
+    support: Vec<bool>,
+    /// Selected column indices, best first
+    selected: Vec<usize>,
+    rankings: Vec<FeatureRanking>,
+    feature_names_in: Option<Vec<String>>,
+}
+
+/// Select features with mRMR inside scikit-learn Pipelines
+///
+/// Args mirror `run_mrmr`; they are validated when `fit` runs, as scikit-learn expects.
+#[pyclass(module = "deep_causality._core")]
+pub struct MrmrSelector {
+    // Kept as the objects passed in: `clone` checks get_params() returns them unchanged
+    max_features: PyObject,
+    min_score: PyObject,
+    normalize_scores: PyObject,
+    n_bins: PyObject,
+    discretization: PyObject,
+    n_threads: PyObject,
+    fitted: Option<Fitted>,
+}
+
+impl MrmrSelector {
+    /// Constructor arguments in `PARAMS` order
+    fn params(&self) -> [&PyObject; 6] {
+        [&self.max_features, &self.min_score, &self.normalize_scores, &self.n_bins, &self.discretization, &self.n_threads]
+    }
+
+    fn fitted(&self) -> PyResult<&Fitted> {
+        // An AttributeError, so hasattr() reports fitted attributes as missing before fit
+        self.fitted.as_ref().ok_or_else(|| PyErr::new::<pyo3::exceptions::PyAttributeError, _>(
+            "This MrmrSelector instance is not fitted yet; call fit first",
+        ))
+    }
+
+    /// `x` as a 2D float64 NumPy array
+    fn as_matrix<'py>(py: Python<'py>, x: &'py PyAny) -> PyResult<&'py PyAny> {
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("dtype", "float64")?;
+        let array = py.import("numpy")?.call_method("asarray", (x,), Some(kwargs))?;
+        if array.getattr("ndim")?.extract::<usize>()? != 2 {
+            return Err(value_err("X must be 2D (samples x features)"));
+        }
+        Ok(array)
+    }
+
+    /// `y` as a 1D float64 NumPy array; labels that aren't numbers are encoded like `encode_columns` does
+    fn as_target<'py>(py: Python<'py>, y: &'py PyAny) -> PyResult<&'py PyAny> {
+        let numpy = py.import("numpy")?;
+        let array = numpy.call_method1("asarray", (y,))?;
+        if array.getattr("ndim")?.extract::<usize>()? != 1 {
+            return Err(value_err("y must be 1D"));
+        }
+        let kind: String = array.getattr("dtype")?.getattr("kind")?.extract()?;
+        let values = if "biuf".contains(kind.as_str()) {
+            array
+        } else {
+            let column = PyDict::new(py);
+            column.set_item("y", array.call_method0("tolist")?)?;
+            let (encoded, classes) = encode_columns(py, column)?;
+            // Codes follow the sorted labels, so with more than two classes the
+            // correlation-based relevance would depend on how the classes are named
+            let n_classes = classes.as_ref(py).get_item("y")?.len()?;
+            if n_classes > 2 {
+                return Err(value_err(format!(
+                    "y has {} classes; mRMR ranks against a binary target only, so compare one class with the rest",
+                    n_classes
+                )));
+            }
+            encoded.as_ref(py).get_item("y")?
+        };
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("dtype", "float64")?;
+        numpy.call_method("asarray", (values,), Some(kwargs))
+    }
+}
+
+#[pymethods]
+impl MrmrSelector {
+    #[new]
+    #[pyo3(signature = (max_features=None, min_score=None, normalize_scores=None, n_bins=None, discretization=None, n_threads=None))]
+    fn new(
+        py: Python,
+        max_features: Option<PyObject>,
+        min_score: Option<PyObject>,
+        normalize_scores: Option<PyObject>,
+        n_bins: Option<PyObject>,
+        discretization: Option<PyObject>,
+        n_threads: Option<PyObject>,
+    ) -> Self {
+        Self {
+            max_features: max_features.unwrap_or_else(|| 10.into_py(py)),
+            min_score: min_score.unwrap_or_else(|| py.None()),
+            normalize_scores: normalize_scores.unwrap_or_else(|| false.into_py(py)),
+            n_bins: n_bins.unwrap_or_else(|| 10.into_py(py)),
+            discretization: discretization.unwrap_or_else(|| py.None()),
+            n_threads: n_threads.unwrap_or_else(|| py.None()),
+            fitted: None,
+        }
+    }
+
+    /// Constructor arguments by name; `deep` is accepted for scikit-learn and ignored
+    #[pyo3(signature = (deep=true))]
+    fn get_params(&self, py: Python, deep: bool) -> PyResult<PyObject> {
+        let _ = deep;
+        let params = PyDict::new(py);
+        for (name, value) in PARAMS.iter().zip(self.params()) {
+            params.set_item(name, value)?;
+        }
+        Ok(params.into())
+    }
+
+    #[pyo3(signature = (**params))]
+    fn set_params<'py>(mut slf: PyRefMut<'py, Self>, params: Option<&PyDict>) -> PyResult<PyRefMut<'py, Self>> {
+        for (key, value) in params.into_iter().flatten() {
+            let name: String = key.extract()?;
+            let value: PyObject = value.into();
+            match name.as_str() {
+                "max_features" => slf.max_features = value,
+                "min_score" => slf.min_score = value,
+                "normalize_scores" => slf.normalize_scores = value,
+                "n_bins" => slf.n_bins = value,
+                "discretization" => slf.discretization = value,
+                "n_threads" => slf.n_threads = value,
+                _ => return Err(value_err(format!(
+                    "Invalid parameter '{}' for MrmrSelector; valid parameters are {}", name, PARAMS.join(", ")
+                ))),
+            }
+        }
+        Ok(slf)
+    }
+
+    /// Rank the columns of `X` (samples x features, a NumPy array or DataFrame) against
+    /// `y` and remember the selected ones; returns self
+    fn fit<'py>(mut slf: PyRefMut<'py, Self>, py: Python<'py>, x: &'py PyAny, y: &'py PyAny) -> PyResult<PyRefMut<'py, Self>> {
+        let feature_names_in: Option<Vec<String>> = match x.getattr("columns") {
+            Ok(columns) => Some(columns.iter()?.map(|c| c?.str()?.extract()).collect::<PyResult<_>>()?),
+            Err(_) => None,
+        };
+        let matrix = Self::as_matrix(py, x)?;
+        let target = Self::as_target(py, y)?;
+        let n_samples: usize = matrix.getattr("shape")?.get_item(0)?.extract()?;
+        if target.len()? != n_samples {
+            return Err(value_err(format!("X has {} samples but y has {}", n_samples, target.len()?)));
+        }
+
+        let n_features: usize = matrix.getattr("shape")?.get_item(1)?.extract()?;
+        let mut names: Vec<String> = feature_names_in.clone()
+            .unwrap_or_else(|| (0..n_features).map(|i| format!("x{}", i)).collect());
+        let mut target_name = String::from("__target__");
+        while names.contains(&target_name) {
+            target_name.push('_');
+        }
+        names.push(target_name.clone());
+
+        let stacked: PyReadonlyArray2<f64> = py.import("numpy")?
+            .call_method1("column_stack", (PyTuple::new(py, [matrix, target]),))?
+            .extract()?;
+        let tensor = numpy_to_tensor(&stacked)?;
+
+        let max_features: usize = slf.max_features.extract(py)?;
+        let min_score: Option<f64> = slf.min_score.extract(py)?;
+        let normalize_scores: bool = slf.normalize_scores.extract(py)?;
+        let n_bins: usize = slf.n_bins.extract(py)?;
+        let discretization: Option<String> = slf.discretization.extract(py)?;
+        let n_threads: Option<usize> = slf.n_threads.extract(py)?;
+        let result = py.allow_threads(|| mrmr_on_tensor(
+            tensor, names.clone(), target_name, max_features, min_score, None, None,
+            normalize_scores, n_bins, discretization, None, n_threads, false,
+        ))?;
+
+        let selected: Vec<usize> = result.features.iter()
+            .filter_map(|ranking| names.iter().position(|n| *n == ranking.name))
+            .collect();
+        let mut support = vec![false; n_features];
+        for &i in &selected {
+            support[i] = true;
+        }
+        slf.fitted = Some(Fitted { support, selected, rankings: result.features, feature_names_in });
+        Ok(slf)
+    }
+
+    /// Keep the selected columns of `X`, in their original order; DataFrames stay DataFrames
+    fn transform<'py>(&self, py: Python<'py>, x: &'py PyAny) -> PyResult<&'py PyAny> {
+        let fitted = self.fitted()?;
+        let indices: Vec<usize> = (0..fitted.support.len()).filter(|&i| fitted.support[i]).collect();
+        if x.hasattr("iloc")? {
+            let n_features: usize = x.getattr("shape")?.get_item(1)?.extract()?;
+            if n_features != fitted.support.len() {
+                return Err(value_err(format!(
+                    "X has {} features but MrmrSelector was fitted with {}", n_features, fitted.support.len()
+                )));
+            }
+            return x.getattr("iloc")?.get_item((PySlice::full(py), indices));
+        }
+
+        let matrix = Self::as_matrix(py, x)?;
+        let n_features: usize = matrix.getattr("shape")?.get_item(1)?.extract()?;
+        if n_features != fitted.support.len() {
+            return Err(value_err(format!(
+                "X has {} features but MrmrSelector was fitted with {}", n_features, fitted.support.len()
+            )));
+        }
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("axis", 1)?;
+        matrix.call_method("take", (indices,), Some(kwargs))
+    }
+
+    fn fit_transform<'py>(slf: PyRefMut<'py, Self>, py: Python<'py>, x: &'py PyAny, y: &'py PyAny) -> PyResult<&'py PyAny> {
+        let slf = Self::fit(slf, py, x, y)?;
+        slf.transform(py, x)
+    }
+
+    /// Boolean mask over the input columns, or the selected column indices with `indices=True`
+    #[pyo3(signature = (indices=false))]
+    fn get_support<'py>(&self, py: Python<'py>, indices: bool) -> PyResult<&'py PyAny> {
+        let fitted = self.fitted()?;
+        let numpy = py.import("numpy")?;
+        if indices {
+            let positions: Vec<usize> = (0..fitted.support.len()).filter(|&i| fitted.support[i]).collect();
+            return numpy.call_method1("array", (positions,));
+        }
+        numpy.call_method1("array", (fitted.support.clone(),))
+    }
+
+    /// Names of the selected columns, from `input_features`, the fitted DataFrame's
+    /// columns, or "x0", "x1", ... in that order of preference
+    #[pyo3(signature = (input_features=None))]
+    fn get_feature_names_out<'py>(&self, py: Python<'py>, input_features: Option<Vec<String>>) -> PyResult<&'py PyAny> {
+        let fitted = self.fitted()?;
+        let names = match input_features.or_else(|| fitted.feature_names_in.clone()) {
+            Some(names) if names.len() == fitted.support.len() => names,
+            Some(names) => return Err(value_err(format!(
+                "Got {} input feature names for {} fitted features", names.len(), fitted.support.len()
+            ))),
+            None => (0..fitted.support.len()).map(|i| format!("x{}", i)).collect(),
+        };
+        let selected: Vec<String> = names.into_iter()
+            .zip(&fitted.support)
+            .filter_map(|(name, &keep)| keep.then_some(name))
+            .collect();
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("dtype", "object")?;
+        py.import("numpy")?.call_method("array", (selected,), Some(kwargs))
+    }
+
+    /// Selected features, best first
+    #[getter]
+    fn rankings_(&self) -> PyResult<Vec<FeatureRanking>> {
+        Ok(self.fitted()?.rankings.clone())
+    }
+
+    /// Selected column indices, best first
+    #[getter]
+    fn selected_features_(&self) -> PyResult<Vec<usize>> {
+        Ok(self.fitted()?.selected.clone())
+    }
+
+    #[getter]
+    fn n_features_in_(&self) -> PyResult<usize> {
+        Ok(self.fitted()?.support.len())
+    }
+
+    /// Only set when fitted on a DataFrame
+    #[getter]
+    fn feature_names_in_(&self) -> PyResult<Vec<String>> {
+        self.fitted()?.feature_names_in.clone()
+            .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyAttributeError, _>("X had no column names"))
+    }
+
+    fn __sklearn_is_fitted__(&self) -> bool {
+        self.fitted.is_some()
+    }
+
+    /// (constructor arguments, msgpack of the fitted state or None), for pickle and joblib
+    fn __getstate__(&self, py: Python) -> PyResult<(PyObject, PyObject)> {
+        let fitted = match &self.fitted {
+            Some(fitted) => serialization::to_msgpack(py, fitted)?,
+            None => py.None(),
+        };
+        Ok((self.get_params(py, false)?, fitted))
+    }
+
+    fn __setstate__(mut slf: PyRefMut<Self>, state: (&PyDict, Option<&[u8]>)) -> PyResult<()> {
+        let (params, fitted) = state;
+        slf.fitted = fitted.map(serialization::from_msgpack).transpose()?;
+        Self::set_params(slf, Some(params))?;
+        Ok(())
+    }
+
+    fn __repr__(&self, py: Python) -> PyResult<String> {
+        let params = PARAMS.iter()
+            .zip(self.params())
+            .map(|(name, value)| Ok(format!("{}={}", name, value.as_ref(py).repr()?)))
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok(format!("MrmrSelector({})", params.join(", ")))
+    }
+}
+
+pub fn register(m: &PyModule) -> PyResult<()> {
+    m.add_class::<MrmrSelector>()?;
+    Ok(())
+}
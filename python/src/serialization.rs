@@ -0,0 +1,81 @@
+//! JSON, msgpack and pickle support for the result classes
+//!
+//! Results can be cached to disk, pickled by joblib or multiprocessing, and sent
+//! between processes. The result classes have no Python constructor, so pickling
+//! goes through `__reduce__`, which rebuilds the object from its msgpack state.
+
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+use pyo3::PyTypeInfo;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+fn value_err(e: impl std::fmt::Display) -> PyErr {
+    PyErr::new::<pyo3::exceptions::PyValueError, _>(e.to_string())
+}
+
+pub(crate) fn to_json<T: Serialize>(value: &T) -> PyResult<String> {
+    serde_json::to_string(value).map_err(value_err)
+}
+
+pub(crate) fn from_json<T: DeserializeOwned>(json: &str) -> PyResult<T> {
+    serde_json::from_str(json).map_err(value_err)
+}
+
+/// msgpack with field names, so stored results survive fields being added
+pub(crate) fn to_msgpack<T: Serialize>(py: Python, value: &T) -> PyResult<PyObject> {
+    let bytes = rmp_serde::to_vec_named(value).map_err(value_err)?;
+    Ok(PyBytes::new(py, &bytes).into())
+}
+
+pub(crate) fn from_msgpack<T: DeserializeOwned>(data: &[u8]) -> PyResult<T> {
+    rmp_serde::from_slice(data).map_err(value_err)
+}
+
+/// `(T.from_msgpack, (state,))`, which pickle calls to restore the object
+pub(crate) fn reduce<T: PyTypeInfo + Serialize>(py: Python, value: &T) -> PyResult<(PyObject, (PyObject,))> {
+    let restore = T::type_object(py).getattr("from_msgpack")?;
+    Ok((restore.into(), (to_msgpack(py, value)?,)))
+}
+
+/// Add `to_json`/`from_json`, `to_msgpack`/`from_msgpack` and pickle support to pyclasses
+/// that implement `Serialize`, `Deserialize` and `Clone`
+macro_rules! impl_serialization {
+    ($($class:ty),+ $(,)?) => {$(
+        #[pyo3::pymethods]
+        impl $class {
+            fn to_json(&self) -> pyo3::PyResult<String> {
+                $crate::serialization::to_json(self)
+            }
+
+            #[classmethod]
+            fn from_json(_cls: &pyo3::types::PyType, json: &str) -> pyo3::PyResult<Self> {
+                $crate::serialization::from_json(json)
+            }
+
+            fn to_msgpack(&self, py: pyo3::Python) -> pyo3::PyResult<pyo3::PyObject> {
+                $crate::serialization::to_msgpack(py, self)
+            }
+
+            #[classmethod]
+            fn from_msgpack(_cls: &pyo3::types::PyType, data: &[u8]) -> pyo3::PyResult<Self> {
+                $crate::serialization::from_msgpack(data)
+            }
+
+            fn __getstate__(&self, py: pyo3::Python) -> pyo3::PyResult<pyo3::PyObject> {
+                $crate::serialization::to_msgpack(py, self)
+            }
+
+            fn __setstate__(&mut self, state: &[u8]) -> pyo3::PyResult<()> {
+                *self = $crate::serialization::from_msgpack(state)?;
+                Ok(())
+            }
+
+            fn __reduce__(&self, py: pyo3::Python) -> pyo3::PyResult<(pyo3::PyObject, (pyo3::PyObject,))> {
+                $crate::serialization::reduce(py, self)
+            }
+        }
+    )+};
+}
+
+pub(crate) use impl_serialization;
@@ -0,0 +1,155 @@
+//! Datasets fed in row chunks, for data larger than memory
+//!
+//! Each chunk is folded into pairwise correlation moments and then dropped, so
+//! memory grows with the number of columns rather than rows. mRMR on the result
+//! scores exactly like a run over all rows at once.
+
+use pyo3::prelude::*;
+use numpy::PyReadonlyArray2;
+use icu_sepsis_backend::causality::{CausalDiscovery, CorrelationMoments, MrmrOptions};
+
+use crate::{
+    backend_err, candidate_columns, numpy_to_tensor, rankings_from_steps, with_python_progress,
+    FeatureRanking, MissingColumnError, MrmrFullResult, TensorShapeError,
+};
+
+/// Column names and the correlation moments of every row appended so far
+#[pyclass]
+pub struct StreamingDataset {
+    moments: CorrelationMoments,
+    column_names: Vec<String>,
+}
+
+#[pymethods]
+impl StreamingDataset {
+    #[new]
+    fn new(column_names: Vec<String>) -> Self {
+        Self { moments: CorrelationMoments::new(column_names.len()), column_names }
+    }
+
+    /// Fold in a 2D float64 NumPy array (rows x columns) in `column_names` order; NaN
+    /// marks a missing value. The array is not kept, so it can be freed right after.
+    fn append_chunk(&mut self, py: Python, chunk: PyReadonlyArray2<f64>) -> PyResult<()> {
+        let (n_rows, n_cols) = chunk.as_array().dim();
+        if n_cols != self.column_names.len() {
+            return Err(TensorShapeError::new_err(format!(
+                "Chunk has {} columns but the dataset has {}", n_cols, self.column_names.len()
+            )));
+        }
+        if n_rows == 0 {
+            return Ok(());
+        }
+        let tensor = numpy_to_tensor(&chunk)?;
+        py.allow_threads(|| self.moments.update(&tensor)).map_err(backend_err)
+    }
+
+    #[getter]
+    fn column_names(&self) -> Vec<String> {
+        self.column_names.clone()
+    }
+
+    /// Rows appended so far
+    #[getter]
+    fn n_rows(&self) -> usize {
+        self.moments.n_rows()
+    }
+
+    /// Run mRMR over every appended row; takes the keyword options of `run_mrmr` except
+    /// discretization, which needs all rows at once
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (target_column, max_features=10, min_score=None, include_columns=None, exclude_columns=None, normalize_scores=false, progress_callback=None, n_threads=None))]
+    fn mrmr(
+        &self,
+        py: Python,
+        target_column: String,
+        max_features: usize,
+        min_score: Option<f64>,
+        include_columns: Option<Vec<String>>,
+        exclude_columns: Option<Vec<String>>,
+        normalize_scores: bool,
+        progress_callback: Option<PyObject>,
+        n_threads: Option<usize>,
+    ) -> PyResult<Vec<FeatureRanking>> {
+        self.mrmr_full(
+            py, target_column, max_features, min_score, include_columns, exclude_columns,
+            normalize_scores, progress_callback, n_threads,
+        )
+        .map(|result| result.features)
+    }
+
+    /// Like `mrmr`, also returning every candidate's score at each step
+    #[allow(clippy::too_many_arguments)]
+    #[pyo3(signature = (target_column, max_features=10, min_score=None, include_columns=None, exclude_columns=None, normalize_scores=false, progress_callback=None, n_threads=None))]
+    fn mrmr_full(
+        &self,
+        py: Python,
+        target_column: String,
+        max_features: usize,
+        min_score: Option<f64>,
+        include_columns: Option<Vec<String>>,
+        exclude_columns: Option<Vec<String>>,
+        normalize_scores: bool,
+        progress_callback: Option<PyObject>,
+        n_threads: Option<usize>,
+    ) -> PyResult<MrmrFullResult> {
+        mrmr_on_moments(
+            py, &self.moments, &self.column_names, target_column, max_features, min_score,
+            include_columns, exclude_columns, normalize_scores, progress_callback, n_threads,
+        )
+    }
+
+    fn __repr__(&self) -> String {
+        format!("StreamingDataset(rows={}, columns={})", self.moments.n_rows(), self.column_names.len())
+    }
+}
+
+/// mRMR over accumulated correlation moments, shared by `StreamingDataset` and
+/// `CausalDataset.update_mrmr`
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn mrmr_on_moments(
+    py: Python,
+    moments: &CorrelationMoments,
+    column_names: &[String],
+    target_column: String,
+    max_features: usize,
+    min_score: Option<f64>,
+    include_columns: Option<Vec<String>>,
+    exclude_columns: Option<Vec<String>>,
+    normalize_scores: bool,
+    progress_callback: Option<PyObject>,
+    n_threads: Option<usize>,
+) -> PyResult<MrmrFullResult> {
+    if !column_names.contains(&target_column) {
+        return Err(MissingColumnError::new_err(format!("Target column '{}' not found", target_column)));
+    }
+    if moments.n_rows() == 0 {
+        return Err(TensorShapeError::new_err("No rows appended yet"));
+    }
+
+    let targets = vec![target_column];
+    let mut options = MrmrOptions::new(max_features);
+    // Filters are checked here so they raise the same errors as in `run_mrmr`
+    if let Some(keep) = candidate_columns(
+        column_names, &targets, include_columns.as_deref(), exclude_columns.as_deref(),
+    )? {
+        let excluded = (0..column_names.len())
+            .filter(|i| !keep.contains(i))
+            .map(|i| column_names[i].clone())
+            .collect();
+        options = options.with_exclude_columns(excluded);
+    }
+    if let Some(n_threads) = n_threads {
+        options = options.with_threads(n_threads);
+    }
+
+    let mut runs = py.allow_threads(|| with_python_progress(&progress_callback, |on_progress| {
+        CausalDiscovery::run_mrmr_moments(moments, column_names, &targets, &options, on_progress)
+    }))?;
+    let (target, steps) = runs.remove(0);
+    rankings_from_steps(steps, min_score, normalize_scores, &target)
+}
+
+pub fn register(m: &PyModule) -> PyResult<()> {
+    m.add_class::<StreamingDataset>()?;
+    Ok(())
+}